@@ -0,0 +1,122 @@
+// Generated by tilepad-manifest, do not edit
+
+/**
+ * Manifest for an icon pack
+ */
+export interface IconsManifest {
+  /**
+   * Version of the manifest structure, manifests without a version are
+   * legacy manifests that must be parsed with migration
+   */
+  manifest_version: number;
+  /**
+   * Definition for the icon pack details
+   */
+  icons: MIconPack;
+  /**
+   * Versions of Tilepad the icon pack supports
+   */
+  engines?: Engines | null;
+  /**
+   * List of icons provided by the icon pack
+   *
+   * Declared under "list" rather than "icons" as the "icons"
+   * key holds the icon pack details
+   */
+  list?: IconList;
+}
+
+/**
+ * Icon pack details for the pack
+ */
+export interface MIconPack {
+  /**
+   * Unique ID of the icon pack (e.g com.jacobtread.tilepad.obs)
+   */
+  id: IconPackId;
+  /**
+   * Name of the icon pack
+   */
+  name: string;
+  /**
+   * Version of the icon pack, semver compatible version number
+   */
+  version: ManifestVersion;
+  /**
+   * List of authors for the pack
+   */
+  authors: string[];
+  /**
+   * Description of the pack
+   */
+  description?: string | null;
+  /**
+   * Icon for the pack
+   */
+  icon?: ManifestPath | null;
+}
+
+/**
+ * Unique ID for an icon pack
+ *
+ * Uses reverse domain syntax (i.e com.example.my-pack)
+ */
+export type IconPackId = string;
+
+/**
+ * Semver compatible version number of a plugin or icon pack (i.e 0.1.0)
+ *
+ * Versions are ordered following semver precedence rules so they
+ * can be used to determine whether an update is available
+ */
+export type ManifestVersion = string;
+
+/**
+ * Path to a file within the plugin or icon pack bundle, relative
+ * to the bundle root directory
+ *
+ * Must use forward slashes as separators (i.e images/icon.svg) and
+ * must not be absolute or escape the bundle root using ".."
+ */
+export type ManifestPath = string;
+
+/**
+ * Versions of the runtimes required by a plugin or icon pack
+ */
+export interface Engines {
+  /**
+   * Range of Tilepad versions supported, all versions are
+   * supported when not specified
+   */
+  tilepad?: TilepadVersion | null;
+}
+
+/**
+ * Version range of Tilepad (i.e >=0.2.0, ^1.0.0)
+ */
+export type TilepadVersion = string;
+
+/**
+ * List of icons within an icon pack
+ *
+ * Icon names must be unique within the list
+ */
+export type IconList = Icon[];
+
+/**
+ * Icon within an icon collection
+ */
+export interface Icon {
+  /**
+   * Path to the icon file
+   */
+  path: ManifestPath;
+  /**
+   * Name of the icon, must be unique within the pack
+   */
+  name: string;
+  /**
+   * Optional tags (keywords) to help find the icon when searching
+   */
+  tags?: string[];
+}
@@ -0,0 +1,454 @@
+// Generated by tilepad-manifest, do not edit
+
+/**
+ * Manifest file format for plugins
+ */
+export interface PluginManifest {
+  /**
+   * Version of the manifest structure, manifests without a version are
+   * legacy manifests that must be parsed with migration
+   */
+  manifest_version: number;
+  /**
+   * Details about the plugin itself
+   */
+  plugin: MPlugin;
+  /**
+   * Versions of Tilepad the plugin supports
+   */
+  engines?: Engines | null;
+  /**
+   * Details for running the plugin
+   * (Option not specified for internal plugins)
+   */
+  bin?: MBin | null;
+  /**
+   * Category for the manifest actions
+   */
+  category: MCategory;
+  /**
+   * Map of available plugin actions
+   */
+  actions: ActionMap;
+  /**
+   * Plugin wide settings shared by all actions (i.e API keys,
+   * server addresses), configured separately from the tiles
+   */
+  settings?: SettingsSchema | null;
+  /**
+   * Permissions the plugin needs, shown to users when
+   * installing the plugin
+   */
+  permissions?: Permissions | null;
+}
+
+/**
+ * Plugin details section of the manifest
+ */
+export interface MPlugin {
+  /**
+   * Unique ID of the plugin (e.g com.jacobtread.tilepad.obs)
+   */
+  id: PluginId;
+  /**
+   * Name of the plugin
+   */
+  name: string;
+  /**
+   * Current version of the plugin, semver compatible version number
+   */
+  version: ManifestVersion;
+  /**
+   * List of authors for the plugin
+   */
+  authors: string[];
+  /**
+   * Description of what the plugin does
+   */
+  description?: string | null;
+  /**
+   * Icon for the plugin
+   */
+  icon?: ManifestPath | null;
+}
+
+/**
+ * Unique ID for a plugin
+ *
+ * Uses reverse domain syntax (i.e com.example.my-plugin)
+ */
+export type PluginId = string;
+
+/**
+ * Semver compatible version number of a plugin or icon pack (i.e 0.1.0)
+ *
+ * Versions are ordered following semver precedence rules so they
+ * can be used to determine whether an update is available
+ */
+export type ManifestVersion = string;
+
+/**
+ * Path to a file within the plugin or icon pack bundle, relative
+ * to the bundle root directory
+ *
+ * Must use forward slashes as separators (i.e images/icon.svg) and
+ * must not be absolute or escape the bundle root using ".."
+ */
+export type ManifestPath = string;
+
+/**
+ * Versions of the runtimes required by a plugin or icon pack
+ */
+export interface Engines {
+  /**
+   * Range of Tilepad versions supported, all versions are
+   * supported when not specified
+   */
+  tilepad?: TilepadVersion | null;
+}
+
+/**
+ * Version range of Tilepad (i.e >=0.2.0, ^1.0.0)
+ */
+export type TilepadVersion = string;
+
+/**
+ * Type of binary the plugin program is using
+ */
+export type MBin = {
+  node: MBinNode;
+} | {
+  native: MBinNative[];
+};
+
+/**
+ * Node "binary" which uses a node runtime to execute the js script
+ * at the provided `entrypoint`
+ */
+export interface MBinNode {
+  /**
+   * Entrypoint for the program
+   *
+   * e.g bin/index.js
+   */
+  entrypoint: ManifestPath;
+  /**
+   * Version of node the program should run using.
+   *
+   * It's recommended that you leave
+   * this as the default unless you explicitly need a specific version
+   * and its features
+   *
+   * @default "22.18.0"
+   */
+  version?: BinaryNodeVersion;
+}
+
+/**
+ * Version range of a node runtime, it's recommended that you leave
+ * this as the default unless you explicitly need a specific version
+ * and its features
+ */
+export type BinaryNodeVersion = string;
+
+/**
+ * Native binary for a specific os + arch combo, contains a
+ * path to the binary
+ */
+export interface MBinNative {
+  os: OperatingSystem;
+  /**
+   * Target Arch this binary should be used for
+   */
+  arch: Arch;
+  /**
+   * Path to the executable file
+   */
+  path: ManifestPath;
+}
+
+/**
+ * Operating systems
+ */
+export type OperatingSystem = "windows" | "macos" | "linux";
+
+/**
+ * CPU architecture the binary is compiled as
+ */
+export type Arch = "x86" | "x64" | "arm" | "arm64";
+
+/**
+ * Definition of the category to place the plugin actions within
+ */
+export interface MCategory {
+  /**
+   * Label for the category in the actions sidebar
+   */
+  label: string;
+  /**
+   * Icon to show in the actions sidebar
+   */
+  icon?: ManifestPath | null;
+}
+
+/**
+ * Ordered map of actions defined within the plugin
+ *
+ * Keys must be unique to each action following
+ * the [a-zA-Z_-] format (i.e example_action, my-action, MyAction)
+ *
+ * Duplicate keys are rejected when deserializing rather than
+ * overwriting the previous action
+ */
+export type ActionMap = Record<string, ManifestAction>;
+
+/**
+ * Manifest action definition
+ */
+export interface ManifestAction {
+  /**
+   * Label for the action, shown in the sidebar
+   */
+  label: string;
+  /**
+   * Icon for the action, shown in the sidebar and
+   * used as the default icon when added to the grid
+   */
+  icon?: ManifestPath | null;
+  /**
+   * Path to a "display" HTML file that can be used to make
+   * the tile into a display tile
+   */
+  display?: ManifestPath | null;
+  /**
+   * Default options for the icon when added to the grid
+   * as a tile
+   */
+  icon_options?: ManifestActionIconOptions | null;
+  /**
+   * Description for the action, shown as a tooltip when hovering
+   * the action
+   */
+  description?: string | null;
+  /**
+   * Path to the inspector HTML file to use for configuring the action
+   *
+   * When not specified the inspector is generated from the
+   * declared settings
+   */
+  inspector?: ManifestPath | null;
+  /**
+   * Settings stored by the action, used to validate and fill
+   * in defaults for the settings of tiles using the action
+   */
+  settings?: SettingsSchema | null;
+}
+
+/**
+ * Default options for an action icon
+ */
+export interface ManifestActionIconOptions {
+  /**
+   * Padding in pixels to pad the icon with
+   */
+  padding?: number | null;
+  /**
+   * Color for the tile background behind the icon
+   */
+  background_color?: Color | null;
+  /**
+   * Color of the tile border
+   */
+  border_color?: Color | null;
+}
+
+/**
+ * Color value in hex, rgb(), rgba(), hsl(), hsla(), hwb(), oklab() or oklch() format. Named colors are only accepted by hosts that allow them
+ */
+export type Color = string;
+
+/**
+ * Ordered map of settings stored by an action or plugin
+ *
+ * Duplicate keys are rejected when deserializing rather than
+ * overwriting the previous setting
+ */
+export type SettingsSchema = Record<string, SettingField>;
+
+/**
+ * Definition of a single setting
+ */
+export type SettingField = ({
+  /**
+   * Label for the setting
+   */
+  label?: string | null;
+  /**
+   * Description of what the setting is used for
+   */
+  description?: string | null;
+  /**
+   * Placeholder shown in the inspector form when the setting is empty
+   */
+  placeholder?: string | null;
+  /**
+   * Whether the setting must always have a value
+   */
+  required?: boolean | null;
+  /**
+   * Whether the value is sensitive (i.e API keys) and must be stored
+   * securely by the host (i.e within the system keychain)
+   *
+   * Only string settings within the plugin settings can be secret
+   */
+  secret?: boolean | null;
+}) & ({
+  /**
+   * Default value for the setting
+   */
+  default?: string | null;
+  /**
+   * Minimum number of characters
+   */
+  min_length?: number | null;
+  /**
+   * Maximum number of characters
+   */
+  max_length?: number | null;
+  type: "string";
+} | {
+  /**
+   * Default value for the setting
+   */
+  default?: number | null;
+  /**
+   * Minimum allowed value (inclusive)
+   */
+  min?: number | null;
+  /**
+   * Maximum allowed value (inclusive)
+   */
+  max?: number | null;
+  /**
+   * Amount the value changes by when stepped in the
+   * inspector form
+   */
+  step?: number | null;
+  type: "number";
+} | {
+  /**
+   * Default value for the setting
+   */
+  default?: boolean | null;
+  type: "bool";
+} | {
+  /**
+   * Options to choose from
+   */
+  options: SettingOption[];
+  /**
+   * Default value for the setting, must be the value
+   * of one of the options
+   */
+  default?: string | null;
+  type: "enum";
+} | {
+  /**
+   * Default value for the setting
+   */
+  default?: Color | null;
+  type: "color";
+} | {
+  /**
+   * File extensions that are accepted (i.e png, jpg), any
+   * file is accepted when empty
+   */
+  extensions?: string[];
+  type: "file";
+});
+
+/**
+ * Option for an enum setting
+ */
+export interface SettingOption {
+  /**
+   * Value stored when the option is chosen
+   */
+  value: string;
+  /**
+   * Label for the option, the value is shown when missing
+   */
+  label?: string | null;
+}
+
+/**
+ * Permissions requested by a plugin
+ */
+export interface Permissions {
+  /**
+   * Hosts the plugin connects to
+   */
+  network?: NetworkHost[];
+  /**
+   * Locations on the filesystem the plugin accesses
+   */
+  filesystem?: FilesystemPermissions;
+  /**
+   * Access the plugin needs to the clipboard
+   */
+  clipboard?: Access | null;
+  /**
+   * Whether the plugin runs shell commands or other programs
+   */
+  shell?: boolean | null;
+  /**
+   * Whether the plugin shows system notifications
+   */
+  notifications?: boolean | null;
+  /**
+   * Whether the plugin simulates keyboard and mouse input
+   */
+  input?: boolean | null;
+}
+
+/**
+ * Host a plugin connects to, optionally including a port
+ * (i.e api.example.com, localhost:8080)
+ *
+ * Subdomains can be matched using a leading wildcard (i.e *.example.com)
+ * and any host can be matched using "*"
+ */
+export type NetworkHost = string;
+
+/**
+ * Locations on the filesystem a plugin accesses
+ *
+ * Each scope can only be requested once
+ */
+export type FilesystemPermissions = FilesystemPermission[];
+
+/**
+ * Access to a location on the filesystem
+ */
+export interface FilesystemPermission {
+  /**
+   * Location being accessed
+   */
+  scope: FilesystemScope;
+  /**
+   * Access needed to the location
+   *
+   * @default "read"
+   */
+  access?: Access;
+}
+
+/**
+ * Location on the filesystem a plugin can access
+ */
+export type FilesystemScope = "documents" | "downloads" | "desktop" | "pictures" | "music" | "videos" | "home" | "temp";
+
+/**
+ * Level of access to a resource
+ */
+export type Access = "read" | "write" | "read_write";
@@ -0,0 +1,428 @@
+//! # Bundle
+//!
+//! Validation of the files referenced by a manifest against the
+//! bundle directory the manifest was loaded from
+
+use crate::{
+    icons::IconsManifest,
+    path::ManifestPath,
+    plugin::{MBin, PluginManifest},
+    system::OperatingSystem,
+};
+use garde::{Path, Report};
+
+/// Allowed extensions for image files
+static IMAGE_EXTENSIONS: [&str; 7] = ["svg", "png", "jpg", "jpeg", "gif", "webp", "ico"];
+
+/// Allowed extensions for HTML files
+static HTML_EXTENSIONS: [&str; 2] = ["html", "htm"];
+
+/// Allowed extensions for node scripts
+static SCRIPT_EXTENSIONS: [&str; 3] = ["js", "mjs", "cjs"];
+
+/// Kind of file an asset path is expected to point to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetKind {
+    /// Image file (svg, png, jpg, ...)
+    Image,
+    /// HTML file (inspector or display)
+    Html,
+    /// Node script
+    Script,
+    /// Native executable for the provided OS
+    Executable(OperatingSystem),
+}
+
+impl AssetKind {
+    /// Allowed file extensions for the asset kind, [None] when
+    /// any extension is allowed
+    pub fn extensions(&self) -> Option<&'static [&'static str]> {
+        match self {
+            AssetKind::Image => Some(&IMAGE_EXTENSIONS),
+            AssetKind::Html => Some(&HTML_EXTENSIONS),
+            AssetKind::Script => Some(&SCRIPT_EXTENSIONS),
+            AssetKind::Executable(_) => None,
+        }
+    }
+}
+
+/// File referenced by a manifest
+#[derive(Debug, Clone)]
+pub struct BundleAsset<'a> {
+    /// Path to the manifest field referencing the file
+    pub field: Path,
+    /// Path to the file within the bundle
+    pub path: &'a ManifestPath,
+    /// Kind of file expected
+    pub kind: AssetKind,
+}
+
+/// Manifest that references files within its bundle
+pub trait BundleManifest {
+    /// Collect all the files referenced by the manifest
+    fn assets(&self) -> Vec<BundleAsset<'_>>;
+}
+
+impl BundleManifest for PluginManifest {
+    fn assets(&self) -> Vec<BundleAsset<'_>> {
+        let mut assets = Vec::new();
+
+        if let Some(icon) = &self.plugin.icon {
+            assets.push(BundleAsset {
+                field: Path::new("plugin").join("icon"),
+                path: icon,
+                kind: AssetKind::Image,
+            });
+        }
+
+        if let Some(icon) = &self.category.icon {
+            assets.push(BundleAsset {
+                field: Path::new("category").join("icon"),
+                path: icon,
+                kind: AssetKind::Image,
+            });
+        }
+
+        for (action_id, action) in self.actions.0.iter() {
+            let action_path = Path::new("actions").join(action_id.as_str());
+            let fields = [
+                ("icon", &action.icon, AssetKind::Image),
+                ("display", &action.display, AssetKind::Html),
+                ("inspector", &action.inspector, AssetKind::Html),
+            ];
+
+            for (name, path, kind) in fields {
+                if let Some(path) = path {
+                    assets.push(BundleAsset {
+                        field: action_path.join(name),
+                        path,
+                        kind,
+                    });
+                }
+            }
+        }
+
+        match &self.bin {
+            Some(MBin::Node { node }) => assets.push(BundleAsset {
+                field: Path::new("bin").join("node").join("entrypoint"),
+                path: &node.entrypoint,
+                kind: AssetKind::Script,
+            }),
+            Some(MBin::Native { native }) => {
+                for (index, binary) in native.iter().enumerate() {
+                    assets.push(BundleAsset {
+                        field: Path::new("bin").join("native").join(index).join("path"),
+                        path: &binary.path,
+                        kind: AssetKind::Executable(binary.os),
+                    });
+                }
+            }
+            None => {}
+        }
+
+        assets
+    }
+}
+
+impl BundleManifest for IconsManifest {
+    fn assets(&self) -> Vec<BundleAsset<'_>> {
+        let mut assets = Vec::new();
+
+        if let Some(icon) = &self.icons.icon {
+            assets.push(BundleAsset {
+                field: Path::new("icons").join("icon"),
+                path: icon,
+                kind: AssetKind::Image,
+            });
+        }
+
+        for (index, icon) in self.list.0.iter().enumerate() {
+            assets.push(BundleAsset {
+                field: Path::new("list").join(index).join("path"),
+                path: &icon.path,
+                kind: AssetKind::Image,
+            });
+        }
+
+        assets
+    }
+}
+
+/// Validate that all the files referenced by the `manifest` exist
+/// within the bundle at `root_dir` and are of the expected type
+pub fn validate_bundle<M>(manifest: &M, root_dir: &std::path::Path) -> Result<(), Report>
+where
+    M: BundleManifest + ?Sized,
+{
+    let mut report = Report::new();
+
+    // Symlinks are followed so assets are checked against the real location
+    let root_dir = match root_dir.canonicalize() {
+        Ok(value) => value,
+        Err(error) => {
+            let message = match error.kind() {
+                std::io::ErrorKind::NotFound => {
+                    format!("bundle directory \"{}\" does not exist", root_dir.display())
+                }
+                _ => format!(
+                    "failed to read bundle directory \"{}\": {error}",
+                    root_dir.display()
+                ),
+            };
+            report.append(Path::empty(), garde::Error::new(message));
+            return Err(report);
+        }
+    };
+
+    for asset in manifest.assets() {
+        if let Err(error) = validate_asset(&asset, &root_dir) {
+            report.append(asset.field, error);
+        }
+    }
+
+    if report.is_empty() {
+        Ok(())
+    } else {
+        Err(report)
+    }
+}
+
+/// Validate a single asset within the bundle, `root_dir` must be canonical
+fn validate_asset(asset: &BundleAsset<'_>, root_dir: &std::path::Path) -> garde::Result {
+    if let Some(extensions) = asset.kind.extensions() {
+        let valid = asset.path.extension().is_some_and(|extension| {
+            extensions
+                .iter()
+                .any(|allowed| extension.eq_ignore_ascii_case(allowed))
+        });
+
+        if !valid {
+            return Err(garde::Error::new(format!(
+                "file \"{}\" must have one of the following extensions: {}",
+                asset.path,
+                extensions.join(", ")
+            )));
+        }
+    }
+
+    let path = asset
+        .path
+        .resolve(root_dir)
+        .map_err(|_| garde::Error::new(format!("path \"{}\" is not valid", asset.path)))?;
+
+    let read_error = |error: std::io::Error| match error.kind() {
+        std::io::ErrorKind::NotFound => {
+            garde::Error::new(format!("file \"{}\" does not exist", asset.path))
+        }
+        _ => garde::Error::new(format!("failed to read file \"{}\": {error}", asset.path)),
+    };
+
+    // Symlinks are followed so the real location must be within the bundle
+    let path = path.canonicalize().map_err(read_error)?;
+    if !path.starts_with(root_dir) {
+        return Err(garde::Error::new(format!(
+            "file \"{}\" is outside of the bundle",
+            asset.path
+        )));
+    }
+
+    let metadata = std::fs::metadata(&path).map_err(read_error)?;
+
+    if !metadata.is_file() {
+        return Err(garde::Error::new(format!(
+            "path \"{}\" is not a file",
+            asset.path
+        )));
+    }
+
+    // Linux binaries must be marked as executable
+    #[cfg(unix)]
+    if asset.kind == AssetKind::Executable(OperatingSystem::Linux) {
+        use std::os::unix::fs::PermissionsExt;
+
+        if metadata.permissions().mode() & 0o111 == 0 {
+            return Err(garde::Error::new(format!(
+                "file \"{}\" is not executable",
+                asset.path
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const MANIFEST: &str = r#"{
+        "manifest_version": 2,
+        "plugin": {
+            "id": "com.example.test",
+            "name": "Test",
+            "version": "0.1.0",
+            "authors": [],
+            "icon": "images/icon.svg"
+        },
+        "bin": { "node": { "entrypoint": "bin/index.js" } },
+        "category": { "label": "Test" },
+        "actions": {
+            "example": {
+                "label": "Example",
+                "display": "display/example.html",
+                "inspector": "inspector/index.html"
+            }
+        }
+    }"#;
+
+    fn write(root: &std::path::Path, path: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "").unwrap();
+    }
+
+    #[test]
+    fn test_valid_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "images/icon.svg");
+        write(dir.path(), "bin/index.js");
+        write(dir.path(), "display/example.html");
+        write(dir.path(), "inspector/index.html");
+
+        let manifest = PluginManifest::parse(MANIFEST).unwrap();
+        assert!(validate_bundle(&manifest, dir.path()).is_ok());
+    }
+
+    #[test]
+    fn test_missing_files_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "images/icon.svg");
+        write(dir.path(), "bin/index.js");
+
+        let manifest = PluginManifest::parse(MANIFEST).unwrap();
+        let report = validate_bundle(&manifest, dir.path()).unwrap_err();
+        let paths: Vec<String> = report.iter().map(|(path, _)| path.to_string()).collect();
+        assert_eq!(
+            paths,
+            ["actions.example.display", "actions.example.inspector"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_outside_bundle_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        write(outside.path(), "index.html");
+
+        write(dir.path(), "images/icon.svg");
+        write(dir.path(), "bin/index.js");
+        write(dir.path(), "display/example.html");
+        std::os::unix::fs::symlink(outside.path(), dir.path().join("inspector")).unwrap();
+
+        let manifest = PluginManifest::parse(MANIFEST).unwrap();
+        let report = validate_bundle(&manifest, dir.path()).unwrap_err();
+        let errors: Vec<(String, String)> = report
+            .iter()
+            .map(|(path, error)| (path.to_string(), error.message().to_string()))
+            .collect();
+        assert_eq!(
+            errors,
+            [(
+                "actions.example.inspector".to_string(),
+                "file \"inspector/index.html\" is outside of the bundle".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_missing_root_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let root_dir = dir.path().join("missing");
+
+        let manifest = PluginManifest::parse(MANIFEST).unwrap();
+        let report = validate_bundle(&manifest, &root_dir).unwrap_err();
+        let errors: Vec<(String, String)> = report
+            .iter()
+            .map(|(path, error)| (path.to_string(), error.message().to_string()))
+            .collect();
+        assert_eq!(
+            errors,
+            [(
+                String::new(),
+                format!("bundle directory \"{}\" does not exist", root_dir.display())
+            )]
+        );
+    }
+
+    #[test]
+    fn test_wrong_extension_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "images/icon.svg");
+        write(dir.path(), "bin/index.js");
+        write(dir.path(), "display/example.txt");
+        write(dir.path(), "inspector/index.html");
+
+        let source = MANIFEST.replace("display/example.html", "display/example.txt");
+        let manifest = PluginManifest::parse(&source).unwrap();
+        let report = validate_bundle(&manifest, dir.path()).unwrap_err();
+        let (path, error) = report.iter().next().unwrap();
+        assert_eq!(path.to_string(), "actions.example.display");
+        assert!(error.message().contains("extensions"));
+    }
+
+    #[test]
+    fn test_icon_pack_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "icons/arrow.svg");
+
+        let manifest = IconsManifest::parse(
+            r#"{
+                "manifest_version": 2,
+                "icons": { "id": "com.example.pack", "name": "Pack", "version": "0.1.0", "authors": [] },
+                "list": [
+                    { "path": "icons/arrow.svg", "name": "Arrow" },
+                    { "path": "icons/circle.svg", "name": "Circle" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let report = validate_bundle(&manifest, dir.path()).unwrap_err();
+        let paths: Vec<String> = report.iter().map(|(path, _)| path.to_string()).collect();
+        assert_eq!(paths, ["list[1].path"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_executable_linux_binary_reported() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "bin/program");
+        write(dir.path(), "bin/program.exe");
+
+        let source = r#"{
+            "manifest_version": 2,
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "0.1.0", "authors": [] },
+            "bin": {
+                "native": [
+                    { "os": "linux", "arch": "x64", "path": "bin/program" },
+                    { "os": "windows", "arch": "x64", "path": "bin/program.exe" }
+                ]
+            },
+            "category": { "label": "Test" },
+            "actions": {}
+        }"#;
+        let manifest = PluginManifest::parse(source).unwrap();
+
+        let report = validate_bundle(&manifest, dir.path()).unwrap_err();
+        let paths: Vec<String> = report.iter().map(|(path, _)| path.to_string()).collect();
+        assert_eq!(paths, ["bin.native[0].path"]);
+
+        let program = dir.path().join("bin/program");
+        fs::set_permissions(&program, fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(validate_bundle(&manifest, dir.path()).is_ok());
+    }
+}
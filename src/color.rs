@@ -0,0 +1,918 @@
+//! # Color
+//!
+//! Color values used within manifests
+
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+/// RGBA color parsed from one of the supported color formats:
+/// - hex
+/// - rgb/rgba
+/// - hsl/hsla
+/// - hwb
+/// - oklab/oklch
+///
+/// Color functions accept both the legacy comma separated syntax and the
+/// CSS Color Level 4 space separated syntax with an optional `/ alpha`
+///
+/// CSS named colors (i.e white, transparent) are only accepted when
+/// using [ColorMode::Lenient]
+///
+/// Serialized in the canonical hex form (#rrggbb or #rrggbbaa when
+/// the color is not fully opaque)
+#[derive(Debug, Clone, Copy)]
+pub struct Color {
+    /// Red component
+    pub red: u8,
+    /// Green component
+    pub green: u8,
+    /// Blue component
+    pub blue: u8,
+    /// Alpha component (255 is fully opaque)
+    pub alpha: u8,
+    /// Whether the color was specified using a named color
+    named: bool,
+}
+
+/// Mode determining which color values are accepted
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Only hex and color functions are accepted
+    #[default]
+    Strict,
+    /// CSS named colors are additionally accepted
+    Lenient,
+}
+
+impl Color {
+    /// Create a fully opaque color from its components
+    pub const fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self::rgba(red, green, blue, u8::MAX)
+    }
+
+    /// Create a color from its components
+    pub const fn rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+            named: false,
+        }
+    }
+
+    /// Whether the color was specified using a CSS named color
+    pub fn is_named(&self) -> bool {
+        self.named
+    }
+
+    /// Parse a color value, named colors are only accepted when
+    /// `mode` is [ColorMode::Lenient]
+    pub fn parse(value: &str, mode: ColorMode) -> Result<Color, garde::Error> {
+        let value = value.trim().to_lowercase();
+
+        if value.is_empty() {
+            return Err(garde::Error::new("empty color"));
+        }
+
+        // Hex
+        if value.starts_with('#') {
+            return parse_hex_color(&value);
+        }
+
+        // Named colors
+        if value.chars().all(|char| char.is_ascii_alphabetic()) {
+            return match mode {
+                ColorMode::Strict => Err(garde::Error::new("named colors are not allowed")),
+                ColorMode::Lenient => parse_named_color(&value),
+            };
+        }
+
+        let (name, args) = parse_function(&value)?;
+
+        match name {
+            "rgb" | "rgba" => parse_rgb_color(name, &args),
+            "hsl" | "hsla" => parse_hsl_color(name, &args),
+            "hwb" => parse_hwb_color(&args),
+            "oklab" => parse_oklab_color(&args),
+            "oklch" => parse_oklch_color(&args),
+            _ => Err(garde::Error::new("invalid color value")),
+        }
+    }
+}
+
+impl PartialEq for Color {
+    fn eq(&self, other: &Self) -> bool {
+        self.red == other.red
+            && self.green == other.green
+            && self.blue == other.blue
+            && self.alpha == other.alpha
+    }
+}
+
+impl Eq for Color {}
+
+impl Hash for Color {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.red, self.green, self.blue, self.alpha).hash(state);
+    }
+}
+
+impl FromStr for Color {
+    type Err = garde::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, ColorMode::Strict)
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)?;
+        if self.alpha != u8::MAX {
+            write!(f, "{:02x}", self.alpha)?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Named colors are accepted here, whether they are allowed
+        // is determined when validating with the desired mode
+        let value = String::deserialize(deserializer)?;
+        Self::parse(&value, ColorMode::Lenient)
+            .map_err(|error| de::Error::custom(format!("invalid color \"{value}\": {error}")))
+    }
+}
+
+impl JsonSchema for Color {
+    fn schema_name() -> Cow<'static, str> {
+        "Color".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "Color value in hex, rgb(), rgba(), hsl(), hsla(), hwb(), oklab() or oklch() format. Named colors are only accepted by hosts that allow them",
+            "type": "string",
+            "examples": ["#ffffff", "rgba(255, 255, 255, 0.5)", "rgb(255 255 255 / 50%)"]
+        })
+    }
+}
+
+/// Parse a hex color
+fn parse_hex_color(value: &str) -> Result<Color, garde::Error> {
+    let value = value
+        .strip_prefix('#')
+        .ok_or_else(|| garde::Error::new("hex color must start with #"))?;
+
+    match value.len() {
+        3 | 4 | 6 | 8 => {}
+        _ => {
+            return Err(garde::Error::new(
+                "hex color must be 3, 4, 6, or 8 hex digits",
+            ));
+        }
+    }
+
+    if !value.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(garde::Error::new("hex color contains invalid characters"));
+    }
+
+    // Expand short forms (i.e #fff) to one byte per component
+    let digits: Vec<u8> = match value.len() {
+        3 | 4 => value.bytes().flat_map(|digit| [digit, digit]).collect(),
+        _ => value.bytes().collect(),
+    };
+
+    let components: Vec<u8> = digits
+        .chunks(2)
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair).unwrap_or_default();
+            u8::from_str_radix(pair, 16).unwrap_or_default()
+        })
+        .collect();
+
+    Ok(Color::rgba(
+        components[0],
+        components[1],
+        components[2],
+        components.get(3).copied().unwrap_or(u8::MAX),
+    ))
+}
+
+/// Parse a CSS named color
+fn parse_named_color(value: &str) -> Result<Color, garde::Error> {
+    let color = match value {
+        "transparent" => Color::rgba(0, 0, 0, 0),
+        "currentcolor" => {
+            return Err(garde::Error::new(
+                "currentcolor is not supported, a concrete color must be specified",
+            ));
+        }
+        _ => NAMED_COLORS
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, color)| *color)
+            .ok_or_else(|| garde::Error::new("unknown named color"))?,
+    };
+
+    Ok(Color {
+        named: true,
+        ..color
+    })
+}
+
+/// CSS named colors (excluding transparent and currentcolor)
+static NAMED_COLORS: [(&str, Color); 148] = [
+    ("aliceblue", Color::rgb(0xf0, 0xf8, 0xff)),
+    ("antiquewhite", Color::rgb(0xfa, 0xeb, 0xd7)),
+    ("aqua", Color::rgb(0x00, 0xff, 0xff)),
+    ("aquamarine", Color::rgb(0x7f, 0xff, 0xd4)),
+    ("azure", Color::rgb(0xf0, 0xff, 0xff)),
+    ("beige", Color::rgb(0xf5, 0xf5, 0xdc)),
+    ("bisque", Color::rgb(0xff, 0xe4, 0xc4)),
+    ("black", Color::rgb(0x00, 0x00, 0x00)),
+    ("blanchedalmond", Color::rgb(0xff, 0xeb, 0xcd)),
+    ("blue", Color::rgb(0x00, 0x00, 0xff)),
+    ("blueviolet", Color::rgb(0x8a, 0x2b, 0xe2)),
+    ("brown", Color::rgb(0xa5, 0x2a, 0x2a)),
+    ("burlywood", Color::rgb(0xde, 0xb8, 0x87)),
+    ("cadetblue", Color::rgb(0x5f, 0x9e, 0xa0)),
+    ("chartreuse", Color::rgb(0x7f, 0xff, 0x00)),
+    ("chocolate", Color::rgb(0xd2, 0x69, 0x1e)),
+    ("coral", Color::rgb(0xff, 0x7f, 0x50)),
+    ("cornflowerblue", Color::rgb(0x64, 0x95, 0xed)),
+    ("cornsilk", Color::rgb(0xff, 0xf8, 0xdc)),
+    ("crimson", Color::rgb(0xdc, 0x14, 0x3c)),
+    ("cyan", Color::rgb(0x00, 0xff, 0xff)),
+    ("darkblue", Color::rgb(0x00, 0x00, 0x8b)),
+    ("darkcyan", Color::rgb(0x00, 0x8b, 0x8b)),
+    ("darkgoldenrod", Color::rgb(0xb8, 0x86, 0x0b)),
+    ("darkgray", Color::rgb(0xa9, 0xa9, 0xa9)),
+    ("darkgreen", Color::rgb(0x00, 0x64, 0x00)),
+    ("darkgrey", Color::rgb(0xa9, 0xa9, 0xa9)),
+    ("darkkhaki", Color::rgb(0xbd, 0xb7, 0x6b)),
+    ("darkmagenta", Color::rgb(0x8b, 0x00, 0x8b)),
+    ("darkolivegreen", Color::rgb(0x55, 0x6b, 0x2f)),
+    ("darkorange", Color::rgb(0xff, 0x8c, 0x00)),
+    ("darkorchid", Color::rgb(0x99, 0x32, 0xcc)),
+    ("darkred", Color::rgb(0x8b, 0x00, 0x00)),
+    ("darksalmon", Color::rgb(0xe9, 0x96, 0x7a)),
+    ("darkseagreen", Color::rgb(0x8f, 0xbc, 0x8f)),
+    ("darkslateblue", Color::rgb(0x48, 0x3d, 0x8b)),
+    ("darkslategray", Color::rgb(0x2f, 0x4f, 0x4f)),
+    ("darkslategrey", Color::rgb(0x2f, 0x4f, 0x4f)),
+    ("darkturquoise", Color::rgb(0x00, 0xce, 0xd1)),
+    ("darkviolet", Color::rgb(0x94, 0x00, 0xd3)),
+    ("deeppink", Color::rgb(0xff, 0x14, 0x93)),
+    ("deepskyblue", Color::rgb(0x00, 0xbf, 0xff)),
+    ("dimgray", Color::rgb(0x69, 0x69, 0x69)),
+    ("dimgrey", Color::rgb(0x69, 0x69, 0x69)),
+    ("dodgerblue", Color::rgb(0x1e, 0x90, 0xff)),
+    ("firebrick", Color::rgb(0xb2, 0x22, 0x22)),
+    ("floralwhite", Color::rgb(0xff, 0xfa, 0xf0)),
+    ("forestgreen", Color::rgb(0x22, 0x8b, 0x22)),
+    ("fuchsia", Color::rgb(0xff, 0x00, 0xff)),
+    ("gainsboro", Color::rgb(0xdc, 0xdc, 0xdc)),
+    ("ghostwhite", Color::rgb(0xf8, 0xf8, 0xff)),
+    ("gold", Color::rgb(0xff, 0xd7, 0x00)),
+    ("goldenrod", Color::rgb(0xda, 0xa5, 0x20)),
+    ("gray", Color::rgb(0x80, 0x80, 0x80)),
+    ("green", Color::rgb(0x00, 0x80, 0x00)),
+    ("greenyellow", Color::rgb(0xad, 0xff, 0x2f)),
+    ("grey", Color::rgb(0x80, 0x80, 0x80)),
+    ("honeydew", Color::rgb(0xf0, 0xff, 0xf0)),
+    ("hotpink", Color::rgb(0xff, 0x69, 0xb4)),
+    ("indianred", Color::rgb(0xcd, 0x5c, 0x5c)),
+    ("indigo", Color::rgb(0x4b, 0x00, 0x82)),
+    ("ivory", Color::rgb(0xff, 0xff, 0xf0)),
+    ("khaki", Color::rgb(0xf0, 0xe6, 0x8c)),
+    ("lavender", Color::rgb(0xe6, 0xe6, 0xfa)),
+    ("lavenderblush", Color::rgb(0xff, 0xf0, 0xf5)),
+    ("lawngreen", Color::rgb(0x7c, 0xfc, 0x00)),
+    ("lemonchiffon", Color::rgb(0xff, 0xfa, 0xcd)),
+    ("lightblue", Color::rgb(0xad, 0xd8, 0xe6)),
+    ("lightcoral", Color::rgb(0xf0, 0x80, 0x80)),
+    ("lightcyan", Color::rgb(0xe0, 0xff, 0xff)),
+    ("lightgoldenrodyellow", Color::rgb(0xfa, 0xfa, 0xd2)),
+    ("lightgray", Color::rgb(0xd3, 0xd3, 0xd3)),
+    ("lightgreen", Color::rgb(0x90, 0xee, 0x90)),
+    ("lightgrey", Color::rgb(0xd3, 0xd3, 0xd3)),
+    ("lightpink", Color::rgb(0xff, 0xb6, 0xc1)),
+    ("lightsalmon", Color::rgb(0xff, 0xa0, 0x7a)),
+    ("lightseagreen", Color::rgb(0x20, 0xb2, 0xaa)),
+    ("lightskyblue", Color::rgb(0x87, 0xce, 0xfa)),
+    ("lightslategray", Color::rgb(0x77, 0x88, 0x99)),
+    ("lightslategrey", Color::rgb(0x77, 0x88, 0x99)),
+    ("lightsteelblue", Color::rgb(0xb0, 0xc4, 0xde)),
+    ("lightyellow", Color::rgb(0xff, 0xff, 0xe0)),
+    ("lime", Color::rgb(0x00, 0xff, 0x00)),
+    ("limegreen", Color::rgb(0x32, 0xcd, 0x32)),
+    ("linen", Color::rgb(0xfa, 0xf0, 0xe6)),
+    ("magenta", Color::rgb(0xff, 0x00, 0xff)),
+    ("maroon", Color::rgb(0x80, 0x00, 0x00)),
+    ("mediumaquamarine", Color::rgb(0x66, 0xcd, 0xaa)),
+    ("mediumblue", Color::rgb(0x00, 0x00, 0xcd)),
+    ("mediumorchid", Color::rgb(0xba, 0x55, 0xd3)),
+    ("mediumpurple", Color::rgb(0x93, 0x70, 0xdb)),
+    ("mediumseagreen", Color::rgb(0x3c, 0xb3, 0x71)),
+    ("mediumslateblue", Color::rgb(0x7b, 0x68, 0xee)),
+    ("mediumspringgreen", Color::rgb(0x00, 0xfa, 0x9a)),
+    ("mediumturquoise", Color::rgb(0x48, 0xd1, 0xcc)),
+    ("mediumvioletred", Color::rgb(0xc7, 0x15, 0x85)),
+    ("midnightblue", Color::rgb(0x19, 0x19, 0x70)),
+    ("mintcream", Color::rgb(0xf5, 0xff, 0xfa)),
+    ("mistyrose", Color::rgb(0xff, 0xe4, 0xe1)),
+    ("moccasin", Color::rgb(0xff, 0xe4, 0xb5)),
+    ("navajowhite", Color::rgb(0xff, 0xde, 0xad)),
+    ("navy", Color::rgb(0x00, 0x00, 0x80)),
+    ("oldlace", Color::rgb(0xfd, 0xf5, 0xe6)),
+    ("olive", Color::rgb(0x80, 0x80, 0x00)),
+    ("olivedrab", Color::rgb(0x6b, 0x8e, 0x23)),
+    ("orange", Color::rgb(0xff, 0xa5, 0x00)),
+    ("orangered", Color::rgb(0xff, 0x45, 0x00)),
+    ("orchid", Color::rgb(0xda, 0x70, 0xd6)),
+    ("palegoldenrod", Color::rgb(0xee, 0xe8, 0xaa)),
+    ("palegreen", Color::rgb(0x98, 0xfb, 0x98)),
+    ("paleturquoise", Color::rgb(0xaf, 0xee, 0xee)),
+    ("palevioletred", Color::rgb(0xdb, 0x70, 0x93)),
+    ("papayawhip", Color::rgb(0xff, 0xef, 0xd5)),
+    ("peachpuff", Color::rgb(0xff, 0xda, 0xb9)),
+    ("peru", Color::rgb(0xcd, 0x85, 0x3f)),
+    ("pink", Color::rgb(0xff, 0xc0, 0xcb)),
+    ("plum", Color::rgb(0xdd, 0xa0, 0xdd)),
+    ("powderblue", Color::rgb(0xb0, 0xe0, 0xe6)),
+    ("purple", Color::rgb(0x80, 0x00, 0x80)),
+    ("rebeccapurple", Color::rgb(0x66, 0x33, 0x99)),
+    ("red", Color::rgb(0xff, 0x00, 0x00)),
+    ("rosybrown", Color::rgb(0xbc, 0x8f, 0x8f)),
+    ("royalblue", Color::rgb(0x41, 0x69, 0xe1)),
+    ("saddlebrown", Color::rgb(0x8b, 0x45, 0x13)),
+    ("salmon", Color::rgb(0xfa, 0x80, 0x72)),
+    ("sandybrown", Color::rgb(0xf4, 0xa4, 0x60)),
+    ("seagreen", Color::rgb(0x2e, 0x8b, 0x57)),
+    ("seashell", Color::rgb(0xff, 0xf5, 0xee)),
+    ("sienna", Color::rgb(0xa0, 0x52, 0x2d)),
+    ("silver", Color::rgb(0xc0, 0xc0, 0xc0)),
+    ("skyblue", Color::rgb(0x87, 0xce, 0xeb)),
+    ("slateblue", Color::rgb(0x6a, 0x5a, 0xcd)),
+    ("slategray", Color::rgb(0x70, 0x80, 0x90)),
+    ("slategrey", Color::rgb(0x70, 0x80, 0x90)),
+    ("snow", Color::rgb(0xff, 0xfa, 0xfa)),
+    ("springgreen", Color::rgb(0x00, 0xff, 0x7f)),
+    ("steelblue", Color::rgb(0x46, 0x82, 0xb4)),
+    ("tan", Color::rgb(0xd2, 0xb4, 0x8c)),
+    ("teal", Color::rgb(0x00, 0x80, 0x80)),
+    ("thistle", Color::rgb(0xd8, 0xbf, 0xd8)),
+    ("tomato", Color::rgb(0xff, 0x63, 0x47)),
+    ("turquoise", Color::rgb(0x40, 0xe0, 0xd0)),
+    ("violet", Color::rgb(0xee, 0x82, 0xee)),
+    ("wheat", Color::rgb(0xf5, 0xde, 0xb3)),
+    ("white", Color::rgb(0xff, 0xff, 0xff)),
+    ("whitesmoke", Color::rgb(0xf5, 0xf5, 0xf5)),
+    ("yellow", Color::rgb(0xff, 0xff, 0x00)),
+    ("yellowgreen", Color::rgb(0x9a, 0xcd, 0x32)),
+];
+
+/// Arguments of a color function
+struct ColorArgs<'a> {
+    /// Color components
+    components: Vec<&'a str>,
+    /// Alpha component if specified
+    alpha: Option<&'a str>,
+    /// Whether the legacy comma separated syntax was used
+    legacy: bool,
+}
+
+/// Parse a color function (i.e rgb(255 0 0 / 50%)) into its name and arguments
+fn parse_function(value: &str) -> Result<(&str, ColorArgs<'_>), garde::Error> {
+    let (name, value) = value
+        .split_once('(')
+        .ok_or_else(|| garde::Error::new("invalid color value"))?;
+
+    // Strip closing
+    let value = value
+        .strip_suffix(")")
+        .ok_or_else(|| garde::Error::new(format!("unclosed {name} color")))?;
+
+    // Legacy comma separated syntax
+    if value.contains(',') {
+        if value.contains('/') {
+            return Err(garde::Error::new(format!(
+                "invalid {name} color, cannot mix commas and /"
+            )));
+        }
+
+        let components = value.split(',').map(|s| s.trim()).collect();
+        return Ok((
+            name,
+            ColorArgs {
+                components,
+                alpha: None,
+                legacy: true,
+            },
+        ));
+    }
+
+    // Space separated syntax with optional alpha
+    let (value, alpha) = match value.split_once('/') {
+        Some((value, alpha)) => (value, Some(alpha.trim())),
+        None => (value, None),
+    };
+
+    if alpha.is_some_and(|alpha| alpha.is_empty() || alpha.contains('/')) {
+        return Err(garde::Error::new(format!("invalid {name} alpha")));
+    }
+
+    Ok((
+        name,
+        ColorArgs {
+            components: value.split_whitespace().collect(),
+            alpha,
+            legacy: false,
+        },
+    ))
+}
+
+impl<'a> ColorArgs<'a> {
+    /// Get the components and alpha for a function with `count` components, when
+    /// `legacy_alpha` is set the legacy syntax requires an additional alpha component
+    /// rather than forbidding one
+    fn split(
+        &self,
+        name: &str,
+        count: usize,
+        legacy_alpha: bool,
+    ) -> Result<(&[&'a str], Option<&'a str>), garde::Error> {
+        if self.legacy {
+            let expected = if legacy_alpha { count + 1 } else { count };
+            if self.components.len() != expected {
+                return Err(garde::Error::new(format!("invalid {name} color")));
+            }
+
+            let (components, alpha) = self.components.split_at(count);
+            return Ok((components, alpha.first().copied()));
+        }
+
+        if self.components.len() != count {
+            return Err(garde::Error::new(format!("invalid {name} color")));
+        }
+
+        Ok((&self.components, self.alpha))
+    }
+
+    /// Ensure the legacy syntax was not used by a function that does not support it
+    fn require_modern(&self, name: &str) -> Result<(), garde::Error> {
+        if self.legacy {
+            return Err(garde::Error::new(format!(
+                "{name} color must use space separated values"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Parse the optional alpha component of a color
+fn parse_optional_alpha(alpha: Option<&str>) -> Result<u8, garde::Error> {
+    alpha.map(parse_alpha).unwrap_or(Ok(u8::MAX))
+}
+
+/// Parse a rgb() or rgba() color
+fn parse_rgb_color(name: &str, args: &ColorArgs<'_>) -> Result<Color, garde::Error> {
+    let (parts, alpha) = args.split(name, 3, name == "rgba")?;
+
+    Ok(Color::rgba(
+        parse_rgb_component(parts[0])?,
+        parse_rgb_component(parts[1])?,
+        parse_rgb_component(parts[2])?,
+        parse_optional_alpha(alpha)?,
+    ))
+}
+
+/// Parse a hsl() or hsla() color
+fn parse_hsl_color(name: &str, args: &ColorArgs<'_>) -> Result<Color, garde::Error> {
+    let (parts, alpha) = args.split(name, 3, name == "hsla")?;
+
+    let hue = parse_hue(parts[0])?;
+    let saturation = parse_percentage(parts[1])?;
+    let lightness = parse_percentage(parts[2])?;
+    let alpha = parse_optional_alpha(alpha)?;
+
+    Ok(hsl_to_color(hue, saturation, lightness, alpha))
+}
+
+/// Parse a hwb() color
+fn parse_hwb_color(args: &ColorArgs<'_>) -> Result<Color, garde::Error> {
+    args.require_modern("hwb")?;
+    let (parts, alpha) = args.split("hwb", 3, false)?;
+
+    let hue = parse_hue(parts[0])?;
+    let mut whiteness = parse_percentage(parts[1])?;
+    let mut blackness = parse_percentage(parts[2])?;
+    let alpha = parse_optional_alpha(alpha)?;
+
+    // Normalize so whiteness and blackness don't exceed 100% combined
+    let total = whiteness + blackness;
+    if total >= 1.0 {
+        whiteness /= total;
+        blackness /= total;
+    }
+
+    let base = hsl_to_color(hue, 1.0, 0.5, alpha);
+    let scale = |component: u8| {
+        unit_to_byte(component as f64 / 255.0 * (1.0 - whiteness - blackness) + whiteness)
+    };
+
+    Ok(Color::rgba(
+        scale(base.red),
+        scale(base.green),
+        scale(base.blue),
+        alpha,
+    ))
+}
+
+/// Parse a oklab() color
+fn parse_oklab_color(args: &ColorArgs<'_>) -> Result<Color, garde::Error> {
+    args.require_modern("oklab")?;
+    let (parts, alpha) = args.split("oklab", 3, false)?;
+
+    let lightness = parse_number_or_percentage(parts[0], 1.0)?;
+    let a = parse_number_or_percentage(parts[1], OK_CHROMA_MAX)?;
+    let b = parse_number_or_percentage(parts[2], OK_CHROMA_MAX)?;
+    let alpha = parse_optional_alpha(alpha)?;
+
+    if !(0.0..=1.0).contains(&lightness) {
+        return Err(garde::Error::new("oklab lightness must be between 0 and 1"));
+    }
+
+    Ok(oklab_to_color(lightness, a, b, alpha))
+}
+
+/// Parse a oklch() color
+fn parse_oklch_color(args: &ColorArgs<'_>) -> Result<Color, garde::Error> {
+    args.require_modern("oklch")?;
+    let (parts, alpha) = args.split("oklch", 3, false)?;
+
+    let lightness = parse_number_or_percentage(parts[0], 1.0)?;
+    let chroma = parse_number_or_percentage(parts[1], OK_CHROMA_MAX)?;
+    let hue = parse_hue(parts[2])?;
+    let alpha = parse_optional_alpha(alpha)?;
+
+    if !(0.0..=1.0).contains(&lightness) {
+        return Err(garde::Error::new("oklch lightness must be between 0 and 1"));
+    }
+
+    if chroma < 0.0 {
+        return Err(garde::Error::new("oklch chroma must not be negative"));
+    }
+
+    let hue = hue.to_radians();
+    Ok(oklab_to_color(
+        lightness,
+        chroma * hue.cos(),
+        chroma * hue.sin(),
+        alpha,
+    ))
+}
+
+/// Chroma value that 100% refers to for oklab() and oklch() colors
+const OK_CHROMA_MAX: f64 = 0.4;
+
+/// Convert HSL components (hue in degrees, saturation and lightness
+/// as 0-1 fractions) into a [Color]
+fn hsl_to_color(hue: f64, saturation: f64, lightness: f64, alpha: u8) -> Color {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = (hue % 360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+
+    let (red, green, blue) = match sector as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    let m = lightness - chroma / 2.0;
+
+    Color::rgba(
+        unit_to_byte(red + m),
+        unit_to_byte(green + m),
+        unit_to_byte(blue + m),
+        alpha,
+    )
+}
+
+/// Convert OKLab components into a [Color], colors outside
+/// of the sRGB gamut are clamped
+fn oklab_to_color(lightness: f64, a: f64, b: f64, alpha: u8) -> Color {
+    let l = (lightness + 0.396_337_777_4 * a + 0.215_803_757_3 * b).powi(3);
+    let m = (lightness - 0.105_561_345_8 * a - 0.063_854_172_8 * b).powi(3);
+    let s = (lightness - 0.089_484_177_5 * a - 1.291_485_548_0 * b).powi(3);
+
+    let red = 4.076_741_662_1 * l - 3.307_711_591_3 * m + 0.230_969_929_2 * s;
+    let green = -1.268_438_004_6 * l + 2.609_757_401_1 * m - 0.341_319_396_5 * s;
+    let blue = -0.004_196_086_3 * l - 0.703_418_614_7 * m + 1.707_614_701_0 * s;
+
+    Color::rgba(
+        unit_to_byte(linear_to_srgb(red)),
+        unit_to_byte(linear_to_srgb(green)),
+        unit_to_byte(linear_to_srgb(blue)),
+        alpha,
+    )
+}
+
+/// Apply the sRGB transfer function to a linear component
+fn linear_to_srgb(value: f64) -> f64 {
+    if value <= 0.003_130_8 {
+        12.92 * value
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Convert a 0-1 fraction into a 0-255 byte
+fn unit_to_byte(value: f64) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Parse a finite number (i.e 10, 0.5, .5)
+fn parse_number(s: &str) -> Result<f64, garde::Error> {
+    // Rust accepts forms such as "inf", "nan" and "1." which are not valid CSS numbers
+    if !is_css_number(s) {
+        return Err(garde::Error::new("invalid number"));
+    }
+
+    match f64::from_str(s) {
+        Ok(value) if value.is_finite() => Ok(value),
+        _ => Err(garde::Error::new("invalid number")),
+    }
+}
+
+/// Check whether `s` follows the CSS number grammar, an optional sign
+/// followed by digits with an optional fraction and exponent (i.e 1.5e2)
+fn is_css_number(s: &str) -> bool {
+    let digits = |s: &str| s.bytes().take_while(u8::is_ascii_digit).count();
+
+    let s = s.strip_prefix(['+', '-']).unwrap_or(s);
+    let integer = digits(s);
+    let mut rest = &s[integer..];
+
+    let mut fraction = 0;
+    if let Some(after) = rest.strip_prefix('.') {
+        fraction = digits(after);
+        if fraction == 0 {
+            return false;
+        }
+        rest = &after[fraction..];
+    }
+
+    if integer == 0 && fraction == 0 {
+        return false;
+    }
+
+    if let Some(after) = rest.strip_prefix(['e', 'E']) {
+        let after = after.strip_prefix(['+', '-']).unwrap_or(after);
+        let exponent = digits(after);
+        if exponent == 0 {
+            return false;
+        }
+        rest = &after[exponent..];
+    }
+
+    rest.is_empty()
+}
+
+/// Parse a number or a percentage where 100% is equal to `max`
+fn parse_number_or_percentage(s: &str, max: f64) -> Result<f64, garde::Error> {
+    if let Some(number) = s.strip_suffix('%') {
+        return parse_number(number)
+            .map(|value| value / 100.0 * max)
+            .map_err(|_| garde::Error::new("invalid percent"));
+    }
+
+    parse_number(s)
+}
+
+/// Parse an RGB component (0–255 or 0–100%)
+fn parse_rgb_component(s: &str) -> Result<u8, garde::Error> {
+    if s.ends_with('%') {
+        return parse_percentage(s).map(unit_to_byte);
+    }
+
+    let value = parse_number(s)?;
+    if value < 0.0 {
+        return Err(garde::Error::new("rgb must not be negative"));
+    }
+
+    if value > 255.0 {
+        return Err(garde::Error::new("rgb exceeded 255 bound"));
+    }
+
+    Ok(value.round() as u8)
+}
+
+/// Parse an alpha channel (0–1 or 0-100%)
+fn parse_alpha(s: &str) -> Result<u8, garde::Error> {
+    let value = if s.ends_with('%') {
+        parse_percentage(s)
+    } else {
+        parse_number(s)
+    };
+
+    match value {
+        Ok(value) if (0.0..=1.0).contains(&value) => Ok(unit_to_byte(value)),
+        _ => Err(garde::Error::new("invalid alpha")),
+    }
+}
+
+/// Parse hue wrapped into 0–360 degrees, supports the deg, rad, grad and turn units
+fn parse_hue(s: &str) -> Result<f64, garde::Error> {
+    let (number, degrees_per_unit) = if let Some(number) = s.strip_suffix("deg") {
+        (number, 1.0)
+    } else if let Some(number) = s.strip_suffix("grad") {
+        (number, 0.9)
+    } else if let Some(number) = s.strip_suffix("rad") {
+        (number, 180.0 / std::f64::consts::PI)
+    } else if let Some(number) = s.strip_suffix("turn") {
+        (number, 360.0)
+    } else {
+        (s, 1.0)
+    };
+
+    let value =
+        parse_number(number).map_err(|_| garde::Error::new("invalid hue"))? * degrees_per_unit;
+
+    // Hues wrap around the color wheel (i.e -30 is 330)
+    Ok(value.rem_euclid(360.0))
+}
+
+/// Parse percentage (0–100%) into a 0-1 fraction
+fn parse_percentage(s: &str) -> Result<f64, garde::Error> {
+    let number = s
+        .strip_suffix('%')
+        .ok_or_else(|| garde::Error::new("missing % sign"))?;
+
+    let value = parse_number(number).map_err(|_| garde::Error::new("invalid percent"))?;
+
+    if value < 0.0 {
+        return Err(garde::Error::new("percent must not be negative"));
+    }
+
+    if value > 100.0 {
+        return Err(garde::Error::new("percent > 100"));
+    }
+
+    Ok(value / 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn color(value: &str) -> Color {
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("Expected OK for {value}"))
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(color("#fff"), Color::rgb(255, 255, 255));
+        assert_eq!(color("#f008"), Color::rgba(255, 0, 0, 0x88));
+        assert_eq!(color("#12AB34"), Color::rgb(0x12, 0xab, 0x34));
+        assert_eq!(color("#12ab3480"), Color::rgba(0x12, 0xab, 0x34, 0x80));
+    }
+
+    #[test]
+    fn test_parse_rgb() {
+        assert_eq!(color("rgb(255, 0, 10)"), Color::rgb(255, 0, 10));
+        assert_eq!(color("rgb(100%, 0%, 50%)"), Color::rgb(255, 0, 128));
+        assert_eq!(color("rgba(0, 0, 0, 0.5)"), Color::rgba(0, 0, 0, 128));
+    }
+
+    #[test]
+    fn test_parse_hsl() {
+        assert_eq!(color("hsl(0, 100%, 50%)"), Color::rgb(255, 0, 0));
+        assert_eq!(color("hsl(120, 100%, 50%)"), Color::rgb(0, 255, 0));
+        assert_eq!(color("hsl(240, 100%, 50%)"), Color::rgb(0, 0, 255));
+        assert_eq!(color("hsl(360, 100%, 50%)"), Color::rgb(255, 0, 0));
+        assert_eq!(color("hsl(0, 0%, 100%)"), Color::rgb(255, 255, 255));
+        assert_eq!(color("hsla(0, 0%, 0%, 0)"), Color::rgba(0, 0, 0, 0));
+    }
+
+    #[test]
+    fn test_canonical_form() {
+        assert_eq!(color("rgb(255, 255, 255)").to_string(), "#ffffff");
+        assert_eq!(color("rgba(255, 0, 0, 0.5)").to_string(), "#ff000080");
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let value: Color = serde_json::from_str("\"hsl(0, 100%, 50%)\"").unwrap();
+        assert_eq!(value, Color::rgb(255, 0, 0));
+        assert_eq!(serde_json::to_string(&value).unwrap(), "\"#ff0000\"");
+
+        assert!(serde_json::from_str::<Color>("\"invalid\"").is_err());
+    }
+
+    #[test]
+    fn test_named_colors_require_lenient_mode() {
+        assert!(Color::parse("white", ColorMode::Strict).is_err());
+
+        let color = Color::parse("White", ColorMode::Lenient).unwrap();
+        assert_eq!(color, Color::rgb(255, 255, 255));
+        assert!(color.is_named());
+
+        assert_eq!(
+            Color::parse("rebeccapurple", ColorMode::Lenient).unwrap(),
+            Color::rgb(0x66, 0x33, 0x99)
+        );
+        assert!(Color::parse("notacolor", ColorMode::Lenient).is_err());
+    }
+
+    #[test]
+    fn test_transparent_and_currentcolor() {
+        assert_eq!(
+            Color::parse("transparent", ColorMode::Lenient).unwrap(),
+            Color::rgba(0, 0, 0, 0)
+        );
+        assert!(Color::parse("currentcolor", ColorMode::Lenient).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_named_color_canonical() {
+        let value: Color = serde_json::from_str("\"white\"").unwrap();
+        assert!(value.is_named());
+        assert_eq!(serde_json::to_string(&value).unwrap(), "\"#ffffff\"");
+    }
+
+    #[test]
+    fn test_parse_space_separated() {
+        assert_eq!(color("rgb(255 0 0 / 50%)"), Color::rgba(255, 0, 0, 128));
+        assert_eq!(color("rgb(127.5 0 0)"), Color::rgb(128, 0, 0));
+        assert_eq!(color("hsl(120deg 100% 50%)"), Color::rgb(0, 255, 0));
+        assert_eq!(
+            color("hsl(0.5turn 100% 50% / 0.25)"),
+            Color::rgba(0, 255, 255, 64)
+        );
+    }
+
+    #[test]
+    fn test_parse_hwb() {
+        assert_eq!(color("hwb(0 0% 0%)"), Color::rgb(255, 0, 0));
+        assert_eq!(color("hwb(0 100% 0%)"), Color::rgb(255, 255, 255));
+        assert_eq!(color("hwb(0 50% 50%)"), Color::rgb(128, 128, 128));
+    }
+
+    #[test]
+    fn test_parse_oklab_and_oklch() {
+        assert_eq!(color("oklab(1 0 0)"), Color::rgb(255, 255, 255));
+        assert_eq!(color("oklab(0% 0 0)"), Color::rgb(0, 0, 0));
+        assert_eq!(color("oklch(62.8% 0.2577 29.23)"), Color::rgb(255, 0, 0));
+        assert_eq!(
+            color("oklch(0.628 0.2577 29.23 / 0)"),
+            Color::rgba(255, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_parse_wrapped_hue() {
+        assert_eq!(color("hsl(-120, 100%, 50%)"), Color::rgb(0, 0, 255));
+        assert_eq!(color("hsl(480deg 100% 50%)"), Color::rgb(0, 255, 0));
+        assert_eq!(color("hsl(-0.5turn 100% 50%)"), color("hsl(180 100% 50%)"));
+        assert_eq!(color("hwb(-360 0% 0%)"), Color::rgb(255, 0, 0));
+    }
+
+    #[test]
+    fn test_parse_exponent_numbers() {
+        assert_eq!(color("rgb(1e2, 2.55e2, 0)"), Color::rgb(100, 255, 0));
+        assert_eq!(color("hsl(1.2E+2 100% 50%)"), Color::rgb(0, 255, 0));
+        assert_eq!(color("rgb(0 0 0 / 5e-1)"), Color::rgba(0, 0, 0, 128));
+
+        assert!(Color::parse("rgb(1e, 0, 0)", ColorMode::Strict).is_err());
+        assert!(Color::parse("rgb(1., 0, 0)", ColorMode::Strict).is_err());
+        assert!(Color::parse("rgb(inf, 0, 0)", ColorMode::Strict).is_err());
+        assert!(Color::parse("rgb(nan, 0, 0)", ColorMode::Strict).is_err());
+    }
+}
@@ -0,0 +1,683 @@
+//! # Diagnostic
+//!
+//! Structured diagnostics for manifest errors, maps errors back to their
+//! line and column within the original manifest source so that editors
+//! and CI tooling can annotate the exact location
+
+use crate::{ManifestError, format::ManifestFormat};
+use json5::char::{is_json5_identifier, is_json5_line_terminator, is_json5_whitespace};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use toml::de::{DeTable, DeValue};
+
+/// Position within the manifest source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Position {
+    /// Line number (Starting at 1)
+    pub line: usize,
+    /// Column number in characters (Starting at 1)
+    pub column: usize,
+}
+
+/// Range within the manifest source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Span {
+    /// Start of the range (inclusive)
+    pub start: Position,
+    /// End of the range (exclusive)
+    pub end: Position,
+}
+
+/// Single problem found within a manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// Message describing the problem
+    pub message: String,
+    /// Path to the field the problem is associated with (i.e actions.my_action.label)
+    pub path: Option<String>,
+    /// Location of the problem within the source, [None] when the
+    /// location could not be determined
+    pub span: Option<Span>,
+}
+
+impl ManifestError {
+    /// Create diagnostics for the error, `source` and `format` must be
+    /// the same manifest source and format that produced the error
+    pub fn diagnostics(&self, source: &str, format: ManifestFormat) -> Vec<Diagnostic> {
+        match self {
+            ManifestError::Utf8(error) => vec![Diagnostic {
+                message: error.to_string(),
+                path: None,
+                span: None,
+            }],
+            ManifestError::Json(error) => vec![json_diagnostic(error)],
+            ManifestError::Json5(error) => vec![json5_diagnostic(error)],
+            ManifestError::Toml(error) => vec![toml_diagnostic(error, source)],
+            ManifestError::Migration(error) => vec![Diagnostic {
+                message: error.to_string(),
+                path: None,
+                span: None,
+            }],
+            ManifestError::TomlSerialize(error) => vec![Diagnostic {
+                message: error.to_string(),
+                path: None,
+                span: None,
+            }],
+            ManifestError::Validation(report) => report_diagnostics(report, source, format),
+        }
+    }
+}
+
+/// Create a diagnostic from a JSON parsing error
+fn json_diagnostic(error: &serde_json::Error) -> Diagnostic {
+    let mut message = error.to_string();
+
+    // Line 0 indicates the error has no position
+    if error.line() == 0 {
+        return Diagnostic {
+            message,
+            path: None,
+            span: None,
+        };
+    }
+
+    // Message already includes the position, we provide that separately
+    let suffix = format!(" at line {} column {}", error.line(), error.column());
+    if let Some(stripped) = message.strip_suffix(&suffix) {
+        message = stripped.to_string();
+    }
+
+    let position = Position {
+        line: error.line(),
+        column: error.column().max(1),
+    };
+
+    Diagnostic {
+        message,
+        path: None,
+        span: Some(Span {
+            start: position,
+            end: position,
+        }),
+    }
+}
+
+/// Create a diagnostic from a JSON5 parsing error
+fn json5_diagnostic(error: &json5::Error) -> Diagnostic {
+    let mut message = error.to_string();
+
+    let Some(position) = error.position() else {
+        return Diagnostic {
+            message,
+            path: None,
+            span: None,
+        };
+    };
+
+    // Message already includes the position, we provide that separately
+    let suffix = format!(" at {position}");
+    if let Some(stripped) = message.strip_suffix(&suffix) {
+        message = stripped.to_string();
+    }
+
+    // JSON5 positions start at 0
+    let position = Position {
+        line: position.line + 1,
+        column: position.column + 1,
+    };
+
+    Diagnostic {
+        message,
+        path: None,
+        span: Some(Span {
+            start: position,
+            end: position,
+        }),
+    }
+}
+
+/// Create a diagnostic from a TOML parsing error
+fn toml_diagnostic(error: &toml::de::Error, source: &str) -> Diagnostic {
+    Diagnostic {
+        message: error.message().trim_end().to_string(),
+        path: None,
+        span: error.span().map(|range| span_of(source, range)),
+    }
+}
+
+/// Create diagnostics for each error within a validation report
+fn report_diagnostics(
+    report: &garde::Report,
+    source: &str,
+    format: ManifestFormat,
+) -> Vec<Diagnostic> {
+    report
+        .iter()
+        .map(|(path, error)| {
+            let segments = segments(path);
+            let range = match format {
+                ManifestFormat::Json | ManifestFormat::Json5 => locate(source, &segments),
+                ManifestFormat::Toml => locate_toml(source, &segments),
+            };
+
+            Diagnostic {
+                message: error.message().to_string(),
+                path: (!path.is_empty()).then(|| path.to_string()),
+                span: range.map(|range| span_of(source, range)),
+            }
+        })
+        .collect()
+}
+
+/// Segment of a path into a manifest document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Segment {
+    /// Object key
+    Key(String),
+    /// Array index
+    Index(usize),
+}
+
+/// Kind of a component within a serialized [garde::Path]
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ComponentKind {
+    None,
+    Key,
+    Index,
+}
+
+/// Split a validation path into its segments
+///
+/// The serialized form of the path is used as it keeps whether each
+/// component is an object key or an array index
+pub(crate) fn segments(path: &garde::Path) -> Vec<Segment> {
+    let components: Vec<(ComponentKind, String)> = serde_json::to_value(path)
+        .and_then(serde_json::from_value)
+        .unwrap_or_default();
+
+    components
+        .into_iter()
+        .filter_map(|(kind, component)| match kind {
+            ComponentKind::Key => Some(Segment::Key(component)),
+            ComponentKind::Index => component.parse().ok().map(Segment::Index),
+            ComponentKind::None => None,
+        })
+        .collect()
+}
+
+/// Convert a byte range within the source into a [Span]
+pub(crate) fn span_of(source: &str, range: Range<usize>) -> Span {
+    Span {
+        start: position_of(source, range.start),
+        end: position_of(source, range.end),
+    }
+}
+
+/// Convert a byte offset within the source into a [Position]
+pub(crate) fn position_of(source: &str, offset: usize) -> Position {
+    let offset = offset.min(source.len());
+    let before = source.get(..offset).unwrap_or(source);
+    let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+
+    Position {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
+/// Locate the byte range of the value at `path` within a TOML `source`
+///
+/// When the full path cannot be found the range of the deepest value
+/// that could be found is used instead
+pub(crate) fn locate_toml(source: &str, path: &[Segment]) -> Option<Range<usize>> {
+    let document = DeTable::parse(source).ok()?;
+    let span = document.span();
+    Some(locate_toml_value(
+        &DeValue::Table(document.into_inner()),
+        span,
+        path,
+    ))
+}
+
+/// Find the value at `path` starting at the TOML `value`
+fn locate_toml_value(value: &DeValue<'_>, span: Range<usize>, path: &[Segment]) -> Range<usize> {
+    let Some((segment, rest)) = path.split_first() else {
+        return span;
+    };
+
+    let found = match (value, segment) {
+        (DeValue::Table(table), Segment::Key(key)) => table
+            .iter()
+            .find(|(current, _)| current.get_ref().as_ref() == key.as_str())
+            .map(|(_, value)| value),
+        (DeValue::Array(array), Segment::Index(index)) => array.get(*index),
+        _ => None,
+    };
+
+    match found {
+        Some(value) => locate_toml_value(value.get_ref(), value.span(), rest),
+        // Fallback to the current value
+        None => span,
+    }
+}
+
+/// Locate the byte range of the value at `path` within a JSON or JSON5 `source`
+///
+/// When the full path cannot be found the range of the deepest value
+/// that could be found is used instead
+pub(crate) fn locate(source: &str, path: &[Segment]) -> Option<Range<usize>> {
+    let mut scanner = Scanner { source, offset: 0 };
+    scanner.skip_whitespace();
+    scanner.locate(path)
+}
+
+/// Minimal JSON5 scanner used to find the location of values, JSON
+/// is a subset of JSON5 so it is also used for JSON sources
+struct Scanner<'a> {
+    source: &'a str,
+    offset: usize,
+}
+
+impl Scanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.source.as_bytes().get(self.offset).copied()
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.source.get(self.offset..)?.chars().next()
+    }
+
+    /// Skip whitespace and comments
+    fn skip_whitespace(&mut self) {
+        loop {
+            let rest = &self.source[self.offset..];
+            if rest.starts_with("//") {
+                let end = rest.find(is_json5_line_terminator).unwrap_or(rest.len());
+                self.offset += end;
+            } else if rest.starts_with("/*") {
+                let end = rest.find("*/").map(|end| end + 2).unwrap_or(rest.len());
+                self.offset += end;
+            } else if let Some(char) = self.peek_char()
+                && is_json5_whitespace(char)
+            {
+                self.offset += char.len_utf8();
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: u8) -> Option<()> {
+        self.skip_whitespace();
+        if self.peek()? != expected {
+            return None;
+        }
+        self.offset += 1;
+        Some(())
+    }
+
+    /// Find the value at `path` starting at the current value
+    fn locate(&mut self, path: &[Segment]) -> Option<Range<usize>> {
+        self.skip_whitespace();
+        let start = self.offset;
+
+        let Some((segment, rest)) = path.split_first() else {
+            self.skip_value()?;
+            return Some(start..self.offset);
+        };
+
+        let found = match (self.peek()?, segment) {
+            (b'{', Segment::Key(key)) => self.find_key(key),
+            (b'[', Segment::Index(index)) => self.find_index(*index),
+            _ => None,
+        };
+
+        if let Some(range) = found.and_then(|_| self.locate(rest)) {
+            return Some(range);
+        }
+
+        // Fallback to the current value
+        self.offset = start;
+        self.skip_value()?;
+        Some(start..self.offset)
+    }
+
+    /// Move to the value for `key` within the object at the current position
+    fn find_key(&mut self, key: &str) -> Option<()> {
+        self.expect(b'{')?;
+        loop {
+            self.skip_whitespace();
+            if self.peek()? == b'}' {
+                return None;
+            }
+
+            let current = self.key()?;
+            self.expect(b':')?;
+            if current == key {
+                return Some(());
+            }
+
+            self.skip_whitespace();
+            self.skip_value()?;
+            self.skip_whitespace();
+            if self.peek()? == b',' {
+                self.offset += 1;
+            }
+        }
+    }
+
+    /// Move to the value at `index` within the array at the current position
+    fn find_index(&mut self, index: usize) -> Option<()> {
+        self.expect(b'[')?;
+        for _ in 0..index {
+            self.skip_whitespace();
+            if self.peek()? == b']' {
+                return None;
+            }
+            self.skip_value()?;
+            self.skip_whitespace();
+            if self.peek()? == b',' {
+                self.offset += 1;
+            }
+        }
+
+        self.skip_whitespace();
+        (self.peek()? != b']').then_some(())
+    }
+
+    /// Skip the value at the current position
+    fn skip_value(&mut self) -> Option<()> {
+        match self.peek()? {
+            b'"' | b'\'' => {
+                self.string()?;
+            }
+            open @ (b'{' | b'[') => {
+                let close = if open == b'{' { b'}' } else { b']' };
+                self.offset += 1;
+                loop {
+                    self.skip_whitespace();
+                    match self.peek()? {
+                        byte if byte == close => {
+                            self.offset += 1;
+                            break;
+                        }
+                        b',' | b':' => self.offset += 1,
+                        _ => self.skip_value()?,
+                    }
+                }
+            }
+            _ => {
+                // Numbers, booleans, null and unquoted keys
+                let start = self.offset;
+                while self.peek().is_some_and(|byte| {
+                    !matches!(byte, b',' | b'}' | b']' | b':' | b'"' | b'\'' | b'/')
+                        && !byte.is_ascii_whitespace()
+                }) {
+                    self.offset += 1;
+                }
+                if start == self.offset {
+                    return None;
+                }
+            }
+        }
+        Some(())
+    }
+
+    /// Read the object key at the current position, JSON5 keys
+    /// may be unquoted identifiers
+    fn key(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        if matches!(self.peek()?, b'"' | b'\'') {
+            return self.string();
+        }
+
+        let start = self.offset;
+        while let Some(char) = self.peek_char()
+            && is_json5_identifier(char)
+        {
+            self.offset += char.len_utf8();
+        }
+
+        (start != self.offset).then(|| self.source[start..self.offset].to_string())
+    }
+
+    /// Read the string at the current position
+    fn string(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        let quote = self.peek()?;
+        if !matches!(quote, b'"' | b'\'') {
+            return None;
+        }
+
+        let start = self.offset;
+        self.offset += 1;
+
+        let mut escaped = false;
+        loop {
+            match self.peek()? {
+                b'\\' => {
+                    escaped = true;
+                    self.offset += 2;
+                }
+                byte if byte == quote => break,
+                _ => self.offset += 1,
+            }
+        }
+
+        self.offset += 1;
+        let raw = self.source.get(start..self.offset)?;
+
+        if !escaped {
+            return Some(raw[1..raw.len() - 1].to_string());
+        }
+
+        // Let json5 handle unescaping
+        json5::from_str(raw).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{format::ManifestFormat, plugin::PluginManifest};
+
+    const MANIFEST: &str = r#"{
+  "manifest_version": 2,
+  "plugin": {
+    "id": "com.example.test",
+    "name": "Test",
+    "version": "0.1.0",
+    "authors": ["Example"]
+  },
+  "category": { "label": "Test" },
+  "actions": {
+    "my_action": {
+      "label": ""
+    }
+  }
+}"#;
+
+    #[test]
+    fn test_validation_error_located() {
+        let error = PluginManifest::parse(MANIFEST).unwrap_err();
+        let diagnostics = error.diagnostics(MANIFEST, ManifestFormat::Json);
+
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = &diagnostics[0];
+        assert_eq!(diagnostic.path.as_deref(), Some("actions.my_action.label"));
+        assert_eq!(
+            diagnostic.span,
+            Some(Span {
+                start: Position {
+                    line: 12,
+                    column: 16
+                },
+                end: Position {
+                    line: 12,
+                    column: 18
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_json_error_located() {
+        let source = "{\n  \"plugin\": {\n    \"id\": 1\n  }\n}";
+        let error = PluginManifest::parse(source).unwrap_err();
+        let diagnostics = error.diagnostics(source, ManifestFormat::Json);
+
+        assert_eq!(diagnostics.len(), 1);
+        let span = diagnostics[0].span.unwrap();
+        assert_eq!(span.start.line, 3);
+        assert!(!diagnostics[0].message.contains("at line"));
+    }
+
+    #[test]
+    fn test_toml_error_located() {
+        let source = "[plugin]\nid = 1\n";
+        let error = PluginManifest::parse_as(source, ManifestFormat::Toml, &Default::default())
+            .unwrap_err();
+        let diagnostics = error.diagnostics(source, ManifestFormat::Toml);
+
+        assert_eq!(diagnostics.len(), 1);
+        let span = diagnostics[0].span.unwrap();
+        assert_eq!(span.start.line, 2);
+    }
+
+    #[test]
+    fn test_json5_error_located() {
+        let source = "{\n  // Comment\n  plugin: { id: 1 },\n}";
+        let error = PluginManifest::parse_as(source, ManifestFormat::Json5, &Default::default())
+            .unwrap_err();
+        let diagnostics = error.diagnostics(source, ManifestFormat::Json5);
+
+        assert_eq!(diagnostics.len(), 1);
+        let span = diagnostics[0].span.unwrap();
+        assert_eq!(span.start.line, 3);
+        assert!(!diagnostics[0].message.contains(" at line"));
+    }
+
+    #[test]
+    fn test_toml_validation_error_located() {
+        let source = r#"
+manifest_version = 2
+
+[plugin]
+id = "com.example.test"
+name = ""
+version = "0.1.0"
+authors = ["Example"]
+
+[category]
+label = "Test"
+
+[actions.example]
+label = "Example"
+"#;
+        let error = PluginManifest::parse_as(source, ManifestFormat::Toml, &Default::default())
+            .unwrap_err();
+        let diagnostics = error.diagnostics(source, ManifestFormat::Toml);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].path.as_deref(), Some("plugin.name"));
+        let span = diagnostics[0].span.unwrap();
+        assert_eq!(span.start, Position { line: 6, column: 8 });
+        assert_eq!(
+            span.end,
+            Position {
+                line: 6,
+                column: 10
+            }
+        );
+    }
+
+    #[test]
+    fn test_json5_validation_error_located() {
+        let source = r#"{
+  // Comment
+  manifest_version: 2,
+  plugin: { id: 'com.example.test', name: 'Test', version: '0.1.0', authors: [], },
+  category: { label: 'Test' },
+  actions: {
+    'my_action': { label: '' },
+  },
+}"#;
+        let error = PluginManifest::parse_as(source, ManifestFormat::Json5, &Default::default())
+            .unwrap_err();
+        let diagnostics = error.diagnostics(source, ManifestFormat::Json5);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].path.as_deref(),
+            Some("actions.my_action.label")
+        );
+        let span = diagnostics[0].span.unwrap();
+        assert_eq!(
+            span.start,
+            Position {
+                line: 7,
+                column: 27
+            }
+        );
+        assert_eq!(
+            span.end,
+            Position {
+                line: 7,
+                column: 29
+            }
+        );
+    }
+
+    #[test]
+    fn test_segments() {
+        let path = garde::Path::new("list").join(1usize).join("name");
+        assert_eq!(
+            segments(&path),
+            [
+                Segment::Key("list".to_string()),
+                Segment::Index(1),
+                Segment::Key("name".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_locate_key_with_separators() {
+        let source = r#"{ "a": { "b": 1 }, "a.b": { "c[0]": "value" } }"#;
+        let path = segments(&garde::Path::new("a.b").join("c[0]"));
+        let range = locate(source, &path);
+        assert_eq!(range.map(|range| &source[range]), Some("\"value\""));
+
+        let source = "[a]\nb = 1\n\n[\"a.b\"]\n\"c[0]\" = \"value\"\n";
+        let range = locate_toml(source, &path);
+        assert_eq!(range.map(|range| &source[range]), Some("\"value\""));
+    }
+
+    #[test]
+    fn test_locate_array_index() {
+        let source = r#"{ "list": [1, { "a": "value" }, 3] }"#;
+        let path = [
+            Segment::Key("list".to_string()),
+            Segment::Index(1),
+            Segment::Key("a".to_string()),
+        ];
+        let range = locate(source, &path);
+        assert_eq!(range.map(|range| &source[range]), Some("\"value\""));
+
+        let source = "list = [1, { a = \"value\" }, 3]\n";
+        let range = locate_toml(source, &path);
+        assert_eq!(range.map(|range| &source[range]), Some("\"value\""));
+    }
+
+    #[test]
+    fn test_locate_falls_back_to_parent() {
+        let source = r#"{ "a": { "b": 1 } }"#;
+        let path = [
+            Segment::Key("a".to_string()),
+            Segment::Key("missing".to_string()),
+        ];
+        let range = locate(source, &path);
+        assert_eq!(range.map(|range| &source[range]), Some(r#"{ "b": 1 }"#));
+    }
+}
@@ -0,0 +1,184 @@
+//! # Engines
+//!
+//! Versions of Tilepad a plugin or icon pack supports, allows hosts
+//! to refuse loading packages built for incompatible versions
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use thiserror::Error;
+
+/// Versions of the runtimes required by a plugin or icon pack
+#[skip_serializing_none]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct Engines {
+    /// Range of Tilepad versions supported, all versions are
+    /// supported when not specified
+    pub tilepad: Option<TilepadVersion>,
+}
+
+impl Engines {
+    /// Check whether the `host_version` of Tilepad is supported
+    ///
+    /// Pre-release host versions (i.e 0.3.0-beta.1) are also accepted
+    /// when their release version is supported
+    pub fn is_compatible_with(
+        &self,
+        host_version: &node_semver::Version,
+    ) -> Result<(), Incompatibility> {
+        let Some(TilepadVersion(required)) = &self.tilepad else {
+            return Ok(());
+        };
+
+        if required.satisfies(host_version) {
+            return Ok(());
+        }
+
+        let mut release = host_version.clone();
+        release.pre_release.clear();
+        release.build.clear();
+        if required.satisfies(&release) {
+            return Ok(());
+        }
+
+        if let Some(minimum) = required.min_version()
+            && release < minimum
+        {
+            return Err(Incompatibility::HostTooOld {
+                required: required.clone(),
+                minimum,
+            });
+        }
+
+        // Host is too new when no later version is supported, the pre-release
+        // is kept so a pre-release of an excluded upper bound is not too new
+        let newer = node_semver::Range::parse(format!(">{host_version}"));
+        if newer.is_ok_and(|newer| required.intersect(&newer).is_none()) {
+            return Err(Incompatibility::HostTooNew {
+                required: required.clone(),
+                host: host_version.clone(),
+            });
+        }
+
+        Err(Incompatibility::Unsupported {
+            required: required.clone(),
+        })
+    }
+}
+
+/// Version range of Tilepad (i.e >=0.2.0, ^1.0.0)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(transparent)]
+#[schemars(with = "String", example = ">=0.2.0")]
+pub struct TilepadVersion(pub node_semver::Range);
+
+impl AsRef<node_semver::Range> for TilepadVersion {
+    fn as_ref(&self) -> &node_semver::Range {
+        &self.0
+    }
+}
+
+/// Reason a Tilepad host version is not supported
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Incompatibility {
+    /// Host is older than the oldest supported version
+    #[error("requires tilepad {minimum} or newer")]
+    HostTooOld {
+        /// Range of supported versions
+        required: node_semver::Range,
+        /// Oldest supported version
+        minimum: node_semver::Version,
+    },
+
+    /// Host is newer than the newest supported version
+    #[error("requires tilepad {required}, {host} is too new")]
+    HostTooNew {
+        /// Range of supported versions
+        required: node_semver::Range,
+        /// Version of the host
+        host: node_semver::Version,
+    },
+
+    /// Host version is not within the supported range
+    #[error("requires tilepad {required}")]
+    Unsupported {
+        /// Range of supported versions
+        required: node_semver::Range,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engines(range: &str) -> Engines {
+        Engines {
+            tilepad: Some(TilepadVersion(node_semver::Range::parse(range).unwrap())),
+        }
+    }
+
+    fn check(engines: &Engines, host: &str) -> Result<(), Incompatibility> {
+        engines.is_compatible_with(&node_semver::Version::parse(host).unwrap())
+    }
+
+    #[test]
+    fn test_compatible_versions() {
+        let engines = engines(">=0.2.0 <1.0.0");
+        assert!(check(&engines, "0.2.0").is_ok());
+        assert!(check(&engines, "0.9.3").is_ok());
+        assert!(check(&engines, "0.3.0-beta.1").is_ok());
+
+        // Any version is supported without a range
+        assert!(check(&Engines::default(), "0.1.0").is_ok());
+    }
+
+    #[test]
+    fn test_incompatible_versions() {
+        let engines = engines(">=0.2.0 <1.0.0");
+
+        let error = check(&engines, "0.1.5").unwrap_err();
+        assert!(matches!(
+            &error,
+            Incompatibility::HostTooOld { minimum, .. } if minimum.to_string() == "0.2.0"
+        ));
+        assert_eq!(error.to_string(), "requires tilepad 0.2.0 or newer");
+
+        let error = check(&engines, "1.0.0").unwrap_err();
+        assert!(matches!(
+            &error,
+            Incompatibility::HostTooNew { host, .. } if host.to_string() == "1.0.0"
+        ));
+        assert_eq!(
+            error.to_string(),
+            "requires tilepad >=0.2.0 <1.0.0, 1.0.0 is too new"
+        );
+
+        // Versions between supported ranges are neither too old nor too new
+        assert!(matches!(
+            check(&self::engines("^0.2.0 || ^0.4.0"), "0.3.0"),
+            Err(Incompatibility::Unsupported { .. })
+        ));
+        assert!(matches!(
+            check(&engines, "0.1.9-beta.1"),
+            Err(Incompatibility::HostTooOld { .. })
+        ));
+
+        // Pre-releases of an excluded upper bound are older than the bound
+        assert!(matches!(
+            check(&engines, "1.0.0-beta.1"),
+            Err(Incompatibility::Unsupported { .. })
+        ));
+        assert!(matches!(
+            check(&engines, "1.0.1-beta.1"),
+            Err(Incompatibility::HostTooNew { .. })
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_engines() {
+        let engines: Engines = serde_json::from_str(r#"{ "tilepad": "^0.2" }"#).unwrap();
+        assert!(check(&engines, "0.2.4").is_ok());
+
+        assert!(serde_json::from_str::<Engines>(r#"{ "tilepad": "not a range" }"#).is_err());
+    }
+}
@@ -0,0 +1,254 @@
+//! # Form
+//!
+//! Renderer agnostic inspector forms generated from the settings declared
+//! by an action, allows hosts to render an inspector for actions that do
+//! not provide their own inspector HTML and for the plugin settings
+
+use crate::settings::{SettingField, SettingKind, SettingsSchema};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Form for configuring the settings of an action or plugin
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InspectorForm {
+    /// Fields in the order they should be displayed
+    pub fields: Vec<FormField>,
+}
+
+impl From<&SettingsSchema> for InspectorForm {
+    fn from(schema: &SettingsSchema) -> Self {
+        let fields = schema
+            .0
+            .iter()
+            .map(|(key, field)| FormField {
+                key: key.to_string(),
+                label: field.label.clone().unwrap_or_else(|| key.to_string()),
+                description: field.description.clone(),
+                placeholder: field.placeholder.clone(),
+                required: field.is_required(),
+                default: field.default_value(),
+                input: FormInput::from(field),
+            })
+            .collect();
+
+        Self { fields }
+    }
+}
+
+/// Field within an [InspectorForm]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormField {
+    /// Key the value of the field is stored under in the settings
+    pub key: String,
+    /// Label for the field, the setting key is used when the
+    /// setting does not declare a label
+    pub label: String,
+    /// Description shown alongside the field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Placeholder shown when the field is empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<String>,
+    /// Whether the field must have a value
+    pub required: bool,
+    /// Initial value for the field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    /// Input used to edit the field along with its constraints
+    #[serde(flatten)]
+    pub input: FormInput,
+}
+
+/// Input used to edit a [FormField]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "input", rename_all = "snake_case")]
+pub enum FormInput {
+    /// Single line text input
+    Text {
+        /// Minimum number of characters
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_length: Option<usize>,
+        /// Maximum number of characters
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_length: Option<usize>,
+    },
+
+    /// Text input that hides its value, used for secret settings
+    Password {
+        /// Minimum number of characters
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_length: Option<usize>,
+        /// Maximum number of characters
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_length: Option<usize>,
+    },
+
+    /// Numeric input
+    Number {
+        /// Minimum allowed value (inclusive)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<f64>,
+        /// Maximum allowed value (inclusive)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<f64>,
+        /// Amount the value changes by when stepped
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        step: Option<f64>,
+    },
+
+    /// On / off toggle
+    Toggle,
+
+    /// Dropdown of options
+    Select {
+        /// Options to choose from
+        options: Vec<FormOption>,
+    },
+
+    /// Color picker
+    Color,
+
+    /// File picker
+    File {
+        /// File extensions that are accepted (i.e png, jpg), any
+        /// file is accepted when empty
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        accept: Vec<String>,
+    },
+}
+
+impl From<&SettingField> for FormInput {
+    fn from(field: &SettingField) -> Self {
+        match &field.kind {
+            SettingKind::String {
+                min_length,
+                max_length,
+                ..
+            } if field.is_secret() => FormInput::Password {
+                min_length: *min_length,
+                max_length: *max_length,
+            },
+            SettingKind::String {
+                min_length,
+                max_length,
+                ..
+            } => FormInput::Text {
+                min_length: *min_length,
+                max_length: *max_length,
+            },
+            SettingKind::Number { min, max, step, .. } => FormInput::Number {
+                min: *min,
+                max: *max,
+                step: *step,
+            },
+            SettingKind::Bool { .. } => FormInput::Toggle,
+            SettingKind::Enum { options, .. } => FormInput::Select {
+                options: options
+                    .iter()
+                    .map(|option| FormOption {
+                        value: option.value.clone(),
+                        label: option.label.clone().unwrap_or_else(|| option.value.clone()),
+                    })
+                    .collect(),
+            },
+            SettingKind::Color { .. } => FormInput::Color,
+            SettingKind::File { extensions } => FormInput::File {
+                accept: extensions.clone(),
+            },
+        }
+    }
+}
+
+/// Option within a [FormInput::Select]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormOption {
+    /// Value stored when the option is chosen
+    pub value: String,
+    /// Label for the option, the value is used when the
+    /// option does not declare a label
+    pub label: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_form_from_settings() {
+        let schema: SettingsSchema = serde_json::from_value(json!({
+            "name": { "type": "string", "label": "Name", "placeholder": "My tile", "max_length": 16 },
+            "speed": { "type": "number", "min": 0, "max": 10, "step": 0.5, "default": 5 },
+            "enabled": { "type": "bool", "default": true },
+            "mode": {
+                "type": "enum",
+                "options": [{ "value": "fast", "label": "Fast" }, { "value": "slow" }]
+            },
+            "color": { "type": "color", "description": "Background color" },
+            "image": { "type": "file", "extensions": ["png"], "required": true }
+        }))
+        .unwrap();
+
+        let form = InspectorForm::from(&schema);
+        assert_eq!(
+            serde_json::to_value(&form).unwrap(),
+            json!({
+                "fields": [
+                    {
+                        "key": "name",
+                        "label": "Name",
+                        "placeholder": "My tile",
+                        "required": false,
+                        "input": "text",
+                        "max_length": 16
+                    },
+                    {
+                        "key": "speed",
+                        "label": "speed",
+                        "required": false,
+                        "default": 5,
+                        "input": "number",
+                        "min": 0.0,
+                        "max": 10.0,
+                        "step": 0.5
+                    },
+                    {
+                        "key": "enabled",
+                        "label": "enabled",
+                        "required": false,
+                        "default": true,
+                        "input": "toggle"
+                    },
+                    {
+                        "key": "mode",
+                        "label": "mode",
+                        "required": false,
+                        "input": "select",
+                        "options": [
+                            { "value": "fast", "label": "Fast" },
+                            { "value": "slow", "label": "slow" }
+                        ]
+                    },
+                    {
+                        "key": "color",
+                        "label": "color",
+                        "description": "Background color",
+                        "required": false,
+                        "input": "color"
+                    },
+                    {
+                        "key": "image",
+                        "label": "image",
+                        "required": true,
+                        "input": "file",
+                        "accept": ["png"]
+                    }
+                ]
+            })
+        );
+
+        // Hosts can read the form back from the JSON output
+        let json = serde_json::to_string(&form).unwrap();
+        assert_eq!(serde_json::from_str::<InspectorForm>(&json).unwrap(), form);
+    }
+}
@@ -0,0 +1,101 @@
+//! # Format
+//!
+//! Serialization formats that manifests can be written in
+
+use crate::ManifestError;
+use serde::{Serialize, de::DeserializeOwned};
+use std::path::Path;
+use strum::{Display, EnumString};
+
+/// Format of a manifest file
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+pub enum ManifestFormat {
+    /// JSON manifest (i.e manifest.json)
+    #[default]
+    #[strum(serialize = "json")]
+    Json,
+    /// Relaxed JSON manifest allowing comments, trailing commas, unquoted
+    /// keys and the rest of the JSON5 syntax (i.e manifest.json5)
+    ///
+    /// Serializes as regular JSON which is also valid JSON5
+    #[strum(serialize = "json5")]
+    Json5,
+    /// TOML manifest (i.e manifest.toml)
+    #[strum(serialize = "toml")]
+    Toml,
+}
+
+impl ManifestFormat {
+    /// File extensions associated with the format
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            ManifestFormat::Json => &["json"],
+            ManifestFormat::Json5 => &["json5", "jsonc"],
+            ManifestFormat::Toml => &["toml"],
+        }
+    }
+
+    /// Detect the format of a manifest from its file name, [None]
+    /// when the extension is not a known manifest format
+    pub fn from_path(path: impl AsRef<Path>) -> Option<ManifestFormat> {
+        let extension = path.as_ref().extension()?.to_str()?;
+        [
+            ManifestFormat::Json,
+            ManifestFormat::Json5,
+            ManifestFormat::Toml,
+        ]
+        .into_iter()
+        .find(|format| {
+            format
+                .extensions()
+                .iter()
+                .any(|allowed| extension.eq_ignore_ascii_case(allowed))
+        })
+    }
+
+    /// Deserialize a value from `source` in this format
+    pub fn deserialize<T>(&self, source: &str) -> Result<T, ManifestError>
+    where
+        T: DeserializeOwned,
+    {
+        Ok(match self {
+            ManifestFormat::Json => serde_json::from_str(source)?,
+            ManifestFormat::Json5 => json5::from_str(source)?,
+            ManifestFormat::Toml => toml::from_str(source)?,
+        })
+    }
+
+    /// Serialize `value` into a string in this format
+    pub fn serialize<T>(&self, value: &T) -> Result<String, ManifestError>
+    where
+        T: Serialize,
+    {
+        Ok(match self {
+            ManifestFormat::Json | ManifestFormat::Json5 => serde_json::to_string_pretty(value)?,
+            ManifestFormat::Toml => toml::to_string_pretty(value)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(
+            ManifestFormat::from_path("plugin/manifest.json"),
+            Some(ManifestFormat::Json)
+        );
+        assert_eq!(
+            ManifestFormat::from_path("plugin/manifest.TOML"),
+            Some(ManifestFormat::Toml)
+        );
+        assert_eq!(
+            ManifestFormat::from_path("plugin/manifest.jsonc"),
+            Some(ManifestFormat::Json5)
+        );
+        assert_eq!(ManifestFormat::from_path("plugin/manifest.yaml"), None);
+        assert_eq!(ManifestFormat::from_path("plugin/manifest"), None);
+    }
+}
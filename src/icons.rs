@@ -2,27 +2,54 @@
 //!
 //! Manifest definition for icon packs
 
-use crate::{ManifestError, validation::validate_id};
-use garde::Validate;
+use crate::{
+    ManifestError,
+    engines::{Engines, Incompatibility},
+    format::ManifestFormat,
+    migration::{MigrationReport, migrate_icons},
+    path::ManifestPath,
+    unknown::unknown_fields,
+    validation::{ValidationOptions, validate_id, validate_manifest_version, validate_text},
+    version::ManifestVersion,
+};
+use garde::{Report, Validate};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use std::{fmt::Display, str::FromStr};
 
 /// Manifest for an icon pack
+#[skip_serializing_none]
 #[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[garde(context(ValidationOptions))]
 pub struct IconsManifest {
+    /// Version of the manifest structure, manifests without a version are
+    /// legacy manifests that must be parsed with migration
+    #[garde(custom(validate_manifest_version))]
+    pub manifest_version: u32,
+
     /// Definition for the icon pack details
     #[garde(dive)]
     pub icons: MIconPack,
+
+    /// Versions of Tilepad the icon pack supports
+    #[garde(skip)]
+    pub engines: Option<Engines>,
+
+    /// List of icons provided by the icon pack
+    ///
+    /// Declared under "list" rather than "icons" as the "icons"
+    /// key holds the icon pack details
+    #[garde(dive)]
+    #[serde(default, skip_serializing_if = "IconList::is_empty")]
+    pub list: IconList,
 }
 
 impl TryFrom<&str> for IconsManifest {
     type Error = ManifestError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let manifest: IconsManifest = serde_json::from_str(value)?;
-        manifest.validate()?;
-        Ok(manifest)
+        Self::parse_with(value, &ValidationOptions::default())
     }
 }
 
@@ -30,60 +57,162 @@ impl TryFrom<&[u8]> for IconsManifest {
     type Error = ManifestError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        let manifest: IconsManifest = serde_json::from_slice(value)?;
-        manifest.validate()?;
-        Ok(manifest)
+        Self::parse_with(std::str::from_utf8(value)?, &ValidationOptions::default())
     }
 }
 
 impl IconsManifest {
-    /// Parse an [IconsManifest] from a string
+    /// Parse an [IconsManifest] from a string using the
+    /// default [ValidationOptions]
     #[inline]
     pub fn parse(value: &str) -> Result<IconsManifest, ManifestError> {
         Self::try_from(value)
     }
+
+    /// Parse an [IconsManifest] from a string validating
+    /// using the provided `options`
+    pub fn parse_with(
+        value: &str,
+        options: &ValidationOptions,
+    ) -> Result<IconsManifest, ManifestError> {
+        Self::parse_as(value, ManifestFormat::Json, options)
+    }
+
+    /// Parse a [IconsManifest] from a string in the provided `format`
+    /// validating using the provided `options`
+    pub fn parse_as(
+        value: &str,
+        format: ManifestFormat,
+        options: &ValidationOptions,
+    ) -> Result<IconsManifest, ManifestError> {
+        let manifest: IconsManifest = format.deserialize(value)?;
+        let mut report = match manifest.validate_with(options) {
+            Ok(()) => Report::new(),
+            Err(report) => report,
+        };
+
+        if options.deny_unknown_fields {
+            for field in unknown_fields::<IconsManifest>(value, format)? {
+                report.append(field.path.clone(), garde::Error::new(field.to_string()));
+            }
+        }
+
+        if !report.is_empty() {
+            return Err(report.into());
+        }
+
+        Ok(manifest)
+    }
+
+    /// Parse a [IconsManifest] from a string in the provided `format`, upgrading
+    /// manifests written for older versions of the manifest structure
+    /// before validating using the provided `options`
+    ///
+    /// Diagnostics for errors are relative to the migrated manifest
+    /// rather than the original `value`
+    pub fn parse_migrated(
+        value: &str,
+        format: ManifestFormat,
+        options: &ValidationOptions,
+    ) -> Result<(IconsManifest, MigrationReport), ManifestError> {
+        let mut value: serde_json::Value = format.deserialize(value)?;
+        let report = migrate_icons(&mut value)?;
+        let value = serde_json::to_string(&value)?;
+        let manifest = Self::parse_as(&value, ManifestFormat::Json, options)?;
+        Ok((manifest, report))
+    }
+
+    /// Serialize the manifest into a string in the provided `format`
+    pub fn to_string_as(&self, format: ManifestFormat) -> Result<String, ManifestError> {
+        format.serialize(self)
+    }
+
+    /// Check whether the `host_version` of Tilepad is supported by the
+    /// icon pack, see [Engines::is_compatible_with]
+    pub fn is_compatible_with(
+        &self,
+        host_version: &node_semver::Version,
+    ) -> Result<(), Incompatibility> {
+        match &self.engines {
+            Some(engines) => engines.is_compatible_with(host_version),
+            None => Ok(()),
+        }
+    }
 }
 
 /// Icon within an icon collection
 #[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[garde(context(ValidationOptions))]
 pub struct Icon {
     /// Path to the icon file
-    #[garde(length(min = 1))]
+    #[garde(dive(()))]
     #[schemars(example = "images/icon.svg")]
-    pub path: String,
+    pub path: ManifestPath,
 
-    /// Name of the icon
-    #[garde(length(min = 1))]
+    /// Name of the icon, must be unique within the pack
+    #[garde(length(min = 1), custom(validate_text))]
     #[schemars(example = "My Icon")]
     pub name: String,
+
+    /// Optional tags (keywords) to help find the icon when searching
+    #[garde(inner(length(min = 1), custom(validate_text)))]
+    #[serde(default, alias = "keywords", skip_serializing_if = "Vec::is_empty")]
+    #[schemars(example = ["arrow", "direction"], extend("x-aliases" = ["keywords"]))]
+    pub tags: Vec<String>,
+}
+
+/// List of icons within an icon pack
+///
+/// Icon names must be unique within the list
+#[derive(Default, Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct IconList(pub Vec<Icon>);
+
+impl IconList {
+    /// Whether the list has no icons
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl AsRef<[Icon]> for IconList {
+    fn as_ref(&self) -> &[Icon] {
+        &self.0
+    }
 }
 
 /// Icon pack details for the pack
 #[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[garde(context(ValidationOptions))]
 pub struct MIconPack {
     /// Unique ID of the icon pack (e.g com.jacobtread.tilepad.obs)
-    #[garde(dive)]
+    #[garde(dive(()))]
     pub id: IconPackId,
     /// Name of the icon pack
-    #[garde(length(min = 1))]
+    #[garde(length(min = 1), custom(validate_text))]
     #[schemars(example = "My Icon Pack")]
     pub name: String,
     /// Version of the icon pack, semver compatible version number
-    #[garde(length(min = 1))]
-    #[schemars(example = "0.1.0")]
-    pub version: String,
+    #[garde(dive)]
+    pub version: ManifestVersion,
     /// List of authors for the pack
-    #[garde(skip)]
+    #[garde(inner(custom(validate_text)))]
     #[schemars(example = ["Example Author 1", "Example Author 2"])]
     pub authors: Vec<String>,
     /// Description of the pack
-    #[garde(skip)]
+    #[garde(inner(custom(validate_text)))]
     #[schemars(example = "My plugin that performs my actions")]
     pub description: Option<String>,
     /// Icon for the pack
-    #[garde(skip)]
+    #[garde(dive(()))]
     #[schemars(example = "images/icon.svg")]
-    pub icon: Option<String>,
+    pub icon: Option<ManifestPath>,
+}
+
+impl MIconPack {
+    /// Check if this icon pack is a newer version than `other`
+    pub fn is_newer_than(&self, other: &MIconPack) -> bool {
+        self.version > other.version
+    }
 }
 
 /// Unique ID for an icon pack
@@ -133,3 +262,121 @@ impl Display for IconPackId {
         self.0.fmt(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::migration::{CURRENT_MANIFEST_VERSION, LEGACY_MANIFEST_VERSION};
+
+    const MANIFEST: &str = r#"{
+        "manifest_version": 2,
+        "icons": {
+            "id": "com.example.pack",
+            "name": "Example Pack",
+            "version": "0.1.0",
+            "authors": []
+        },
+        "list": [
+            { "path": "icons/arrow.svg", "name": "Arrow", "tags": ["direction"] },
+            { "path": "icons/circle.svg", "name": "Circle", "keywords": ["shape"] }
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_icon_list() {
+        let manifest = IconsManifest::parse(MANIFEST).unwrap();
+        assert_eq!(manifest.list.0.len(), 2);
+        assert_eq!(manifest.list.0[0].tags, ["direction"]);
+        assert_eq!(manifest.list.0[1].tags, ["shape"]);
+    }
+
+    #[test]
+    fn test_parse_from_bytes() {
+        let manifest = IconsManifest::try_from(MANIFEST.as_bytes()).unwrap();
+        assert_eq!(manifest.list.0.len(), 2);
+
+        assert!(matches!(
+            IconsManifest::try_from(&[0xff, 0xfe][..]),
+            Err(ManifestError::Utf8(_))
+        ));
+    }
+
+    #[test]
+    fn test_engines_compatibility() {
+        let manifest = IconsManifest::parse(
+            r#"{
+                "manifest_version": 2,
+                "icons": { "id": "com.example.pack", "name": "Pack", "version": "0.1.0", "authors": [] },
+                "engines": { "tilepad": ">=0.2.0" }
+            }"#,
+        )
+        .unwrap();
+
+        let host = node_semver::Version::parse("0.1.0").unwrap();
+        assert!(matches!(
+            manifest.is_compatible_with(&host),
+            Err(Incompatibility::HostTooOld { .. })
+        ));
+
+        // Manifests without engines support every version
+        let manifest = IconsManifest::parse(MANIFEST).unwrap();
+        assert!(manifest.is_compatible_with(&host).is_ok());
+        assert!(
+            !manifest
+                .to_string_as(ManifestFormat::Json)
+                .unwrap()
+                .contains("engines")
+        );
+    }
+
+    #[test]
+    fn test_icon_list_optional() {
+        let manifest = IconsManifest::parse(
+            r#"{ "manifest_version": 2, "icons": { "id": "com.example.pack", "name": "Pack", "version": "0.1.0", "authors": [] } }"#,
+        )
+        .unwrap();
+        assert!(manifest.list.is_empty());
+
+        // Empty lists are not written back out
+        let output = manifest.to_string_as(ManifestFormat::Json).unwrap();
+        assert!(!output.contains("\"list\""));
+    }
+
+    #[test]
+    fn test_icon_list_rejects_duplicate_names() {
+        let source = MANIFEST.replace("\"Circle\"", "\"Arrow\"");
+        let error = IconsManifest::parse(&source).unwrap_err();
+        let ManifestError::Validation(report) = error else {
+            panic!("expected validation error");
+        };
+
+        let (path, error) = report.iter().next().unwrap();
+        assert_eq!(path.to_string(), "list[1].name");
+        assert!(error.message().contains("duplicate icon name"));
+    }
+
+    #[test]
+    fn test_icon_list_rejects_empty_path() {
+        let source = MANIFEST.replace("icons/arrow.svg", "");
+        assert!(IconsManifest::parse(&source).is_err());
+    }
+    #[test]
+    fn test_missing_manifest_version_is_legacy() {
+        let source = MANIFEST.replace("\"manifest_version\": 2,", "");
+
+        let Err(ManifestError::Json(error)) = IconsManifest::parse(&source) else {
+            panic!("expected missing manifest_version error");
+        };
+        assert!(
+            error
+                .to_string()
+                .contains("missing field `manifest_version`")
+        );
+
+        let (manifest, report) =
+            IconsManifest::parse_migrated(&source, ManifestFormat::Json, &Default::default())
+                .unwrap();
+        assert_eq!(report.from_version, LEGACY_MANIFEST_VERSION);
+        assert_eq!(manifest.manifest_version, CURRENT_MANIFEST_VERSION);
+    }
+}
@@ -1,34 +1,64 @@
 use thiserror::Error;
 
+pub mod bundle;
+pub mod color;
+pub mod diagnostic;
+pub mod engines;
+pub mod form;
+pub mod format;
 pub mod icons;
+pub mod lint;
+pub mod migration;
+pub mod path;
+pub mod permissions;
 pub mod plugin;
+pub mod scaffold;
+pub mod schema;
+pub mod settings;
+pub mod streamdeck;
 pub mod system;
+pub mod typescript;
+pub mod unknown;
 pub mod validation;
+pub mod version;
 
 /// Errors that can occur when parsing the manifest
 #[derive(Debug, Error)]
 pub enum ManifestError {
+    #[error(transparent)]
+    Utf8(#[from] std::str::Utf8Error),
     #[error(transparent)]
     Json(#[from] serde_json::Error),
     #[error(transparent)]
+    Json5(#[from] json5::Error),
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+    #[error(transparent)]
+    TomlSerialize(#[from] toml::ser::Error),
+    #[error(transparent)]
+    Migration(#[from] migration::MigrationError),
+    #[error(transparent)]
     Validation(#[from] garde::Report),
 }
 
 #[test]
 fn generate_schema() {
-    use schemars::generate::SchemaSettings;
     use std::path::Path;
 
     let schemas_path = Path::new("schemas");
     std::fs::create_dir_all(schemas_path).unwrap();
 
+    let schema = schema::icons_schema();
     let schema_path = schemas_path.join("icons.json");
-    let generator = SchemaSettings::draft07().into_generator();
-    let schema = generator.into_root_schema_for::<icons::IconsManifest>();
     std::fs::write(schema_path, serde_json::to_string_pretty(&schema).unwrap()).unwrap();
 
+    let schema = schema::plugin_schema();
     let schema_path = schemas_path.join("plugins.json");
-    let generator = SchemaSettings::draft07().into_generator();
-    let schema = generator.into_root_schema_for::<plugin::PluginManifest>();
     std::fs::write(schema_path, serde_json::to_string_pretty(&schema).unwrap()).unwrap();
+
+    let types_path = schemas_path.join("icons.d.ts");
+    std::fs::write(types_path, typescript::icons_typescript()).unwrap();
+
+    let types_path = schemas_path.join("plugins.d.ts");
+    std::fs::write(types_path, typescript::plugin_typescript()).unwrap();
 }
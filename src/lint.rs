@@ -0,0 +1,399 @@
+//! # Lint
+//!
+//! Non-fatal quality checks for manifests. Unlike validation errors lints
+//! do not prevent a manifest from loading, they highlight issues that
+//! authors should consider fixing (i.e missing descriptions or icons)
+
+use crate::{
+    ManifestError,
+    format::ManifestFormat,
+    icons::IconsManifest,
+    plugin::PluginManifest,
+    unknown::{UnknownField, unknown_fields},
+    validation::ValidationOptions,
+};
+use garde::Path;
+use serde::Serialize;
+use strum::{Display, EnumString};
+
+/// Labels longer than this are likely to be truncated in the UI
+pub const MAX_LABEL_LENGTH: usize = 32;
+
+/// Severity of a lint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display, EnumString, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Suggestion that may improve the manifest
+    #[strum(serialize = "info")]
+    Info,
+    /// Issue that should be addressed
+    #[strum(serialize = "warning")]
+    Warning,
+}
+
+/// Code identifying the kind of lint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintCode {
+    /// Description was not provided
+    #[strum(serialize = "missing-description")]
+    MissingDescription,
+    /// Icon was not provided
+    #[strum(serialize = "missing-icon")]
+    MissingIcon,
+    /// No authors were provided
+    #[strum(serialize = "missing-authors")]
+    MissingAuthors,
+    /// Action does not have an inspector
+    #[strum(serialize = "missing-inspector")]
+    MissingInspector,
+    /// Label is likely to be truncated
+    #[strum(serialize = "long-label")]
+    LongLabel,
+    /// Icon pack does not list any icons
+    #[strum(serialize = "empty-icon-list")]
+    EmptyIconList,
+    /// Field is not known and will be ignored
+    #[strum(serialize = "unknown-field")]
+    UnknownField,
+}
+
+/// Single lint produced for a manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Lint {
+    /// Code for the lint
+    pub code: LintCode,
+    /// Severity of the lint
+    pub severity: Severity,
+    /// Path to the field the lint applies to (i.e actions.my_action.inspector)
+    pub path: String,
+    /// Message describing the lint
+    pub message: String,
+}
+
+impl Lint {
+    fn new(code: LintCode, severity: Severity, path: Path, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            severity,
+            path: path.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Unknown fields are reported as warnings when they are
+/// not denied by the [ValidationOptions](crate::validation::ValidationOptions)
+impl From<UnknownField> for Lint {
+    fn from(value: UnknownField) -> Self {
+        let message = value.to_string();
+        Lint::new(
+            LintCode::UnknownField,
+            Severity::Warning,
+            value.path,
+            message,
+        )
+    }
+}
+
+impl PluginManifest {
+    /// Parse the manifest `source` in the provided `format` and check it for
+    /// non-fatal quality issues, including fields that are not known
+    pub fn lint_source(source: &str, format: ManifestFormat) -> Result<Vec<Lint>, ManifestError> {
+        let manifest = Self::parse_as(source, format, &ValidationOptions::default())?;
+        let mut lints: Vec<Lint> = unknown_fields::<Self>(source, format)?
+            .into_iter()
+            .map(Lint::from)
+            .collect();
+        lints.extend(manifest.lint());
+        Ok(lints)
+    }
+
+    /// Check the manifest for non-fatal quality issues
+    pub fn lint(&self) -> Vec<Lint> {
+        let mut lints = Vec::new();
+        let plugin = &self.plugin;
+
+        if plugin.description.is_none() {
+            lints.push(Lint::new(
+                LintCode::MissingDescription,
+                Severity::Warning,
+                Path::new("plugin").join("description"),
+                "plugin should have a description",
+            ));
+        }
+
+        if plugin.icon.is_none() {
+            lints.push(Lint::new(
+                LintCode::MissingIcon,
+                Severity::Warning,
+                Path::new("plugin").join("icon"),
+                "plugin should have an icon",
+            ));
+        }
+
+        if plugin.authors.is_empty() {
+            lints.push(Lint::new(
+                LintCode::MissingAuthors,
+                Severity::Warning,
+                Path::new("plugin").join("authors"),
+                "plugin should list at least one author",
+            ));
+        }
+
+        if self.category.icon.is_none() {
+            lints.push(Lint::new(
+                LintCode::MissingIcon,
+                Severity::Info,
+                Path::new("category").join("icon"),
+                "category should have an icon",
+            ));
+        }
+
+        lint_label(
+            &mut lints,
+            &self.category.label,
+            Path::new("category").join("label"),
+        );
+
+        for (action_id, action) in self.actions.0.iter() {
+            let path = Path::new("actions").join(action_id.as_str());
+
+            lint_label(&mut lints, &action.label, path.join("label"));
+
+            if action.description.is_none() {
+                lints.push(Lint::new(
+                    LintCode::MissingDescription,
+                    Severity::Info,
+                    path.join("description"),
+                    "action should have a description to show as a tooltip",
+                ));
+            }
+
+            if action.icon.is_none() {
+                lints.push(Lint::new(
+                    LintCode::MissingIcon,
+                    Severity::Info,
+                    path.join("icon"),
+                    "action should have an icon",
+                ));
+            }
+
+            if action.inspector.is_none() && action.settings.is_none() {
+                lints.push(Lint::new(
+                    LintCode::MissingInspector,
+                    Severity::Info,
+                    path.join("inspector"),
+                    "action has no inspector, it will not be configurable",
+                ));
+            }
+        }
+
+        lints
+    }
+}
+
+impl IconsManifest {
+    /// Parse the manifest `source` in the provided `format` and check it for
+    /// non-fatal quality issues, including fields that are not known
+    pub fn lint_source(source: &str, format: ManifestFormat) -> Result<Vec<Lint>, ManifestError> {
+        let manifest = Self::parse_as(source, format, &ValidationOptions::default())?;
+        let mut lints: Vec<Lint> = unknown_fields::<Self>(source, format)?
+            .into_iter()
+            .map(Lint::from)
+            .collect();
+        lints.extend(manifest.lint());
+        Ok(lints)
+    }
+
+    /// Check the manifest for non-fatal quality issues
+    pub fn lint(&self) -> Vec<Lint> {
+        let mut lints = Vec::new();
+        let pack = &self.icons;
+
+        if pack.description.is_none() {
+            lints.push(Lint::new(
+                LintCode::MissingDescription,
+                Severity::Warning,
+                Path::new("icons").join("description"),
+                "icon pack should have a description",
+            ));
+        }
+
+        if pack.icon.is_none() {
+            lints.push(Lint::new(
+                LintCode::MissingIcon,
+                Severity::Warning,
+                Path::new("icons").join("icon"),
+                "icon pack should have an icon",
+            ));
+        }
+
+        if pack.authors.is_empty() {
+            lints.push(Lint::new(
+                LintCode::MissingAuthors,
+                Severity::Warning,
+                Path::new("icons").join("authors"),
+                "icon pack should list at least one author",
+            ));
+        }
+
+        if self.list.0.is_empty() {
+            lints.push(Lint::new(
+                LintCode::EmptyIconList,
+                Severity::Warning,
+                Path::new("list"),
+                "icon pack does not list any icons",
+            ));
+        }
+
+        lints
+    }
+}
+
+/// Lint a label for being too long
+fn lint_label(lints: &mut Vec<Lint>, label: &str, path: Path) {
+    let length = label.chars().count();
+    if length > MAX_LABEL_LENGTH {
+        lints.push(Lint::new(
+            LintCode::LongLabel,
+            Severity::Warning,
+            path,
+            format!(
+                "label is {length} characters long, labels longer than {MAX_LABEL_LENGTH} characters may be truncated"
+            ),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(lints: &[Lint]) -> Vec<(LintCode, &str)> {
+        lints
+            .iter()
+            .map(|lint| (lint.code, lint.path.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_lint_complete_plugin() {
+        let manifest = PluginManifest::parse(
+            r#"{
+                "manifest_version": 2,
+                "plugin": {
+                    "id": "com.example.test",
+                    "name": "Test",
+                    "version": "0.1.0",
+                    "authors": ["Example"],
+                    "description": "Example plugin",
+                    "icon": "images/icon.svg"
+                },
+                "category": { "label": "Test", "icon": "images/icon.svg" },
+                "actions": {
+                    "example": {
+                        "label": "Example",
+                        "description": "Example action",
+                        "icon": "images/example.svg",
+                        "inspector": "inspector/index.html"
+                    },
+                    "generated": {
+                        "label": "Generated",
+                        "description": "Action using a generated inspector",
+                        "icon": "images/generated.svg",
+                        "settings": { "enabled": { "type": "bool" } }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert!(manifest.lint().is_empty());
+    }
+
+    #[test]
+    fn test_lint_incomplete_plugin() {
+        let manifest = PluginManifest::parse(
+            r#"{
+                "manifest_version": 2,
+                "plugin": { "id": "com.example.test", "name": "Test", "version": "0.1.0", "authors": [] },
+                "category": { "label": "Test" },
+                "actions": {
+                    "example": { "label": "An action label that is far too long to display" }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let lints = manifest.lint();
+        assert_eq!(
+            codes(&lints),
+            [
+                (LintCode::MissingDescription, "plugin.description"),
+                (LintCode::MissingIcon, "plugin.icon"),
+                (LintCode::MissingAuthors, "plugin.authors"),
+                (LintCode::MissingIcon, "category.icon"),
+                (LintCode::LongLabel, "actions.example.label"),
+                (LintCode::MissingDescription, "actions.example.description"),
+                (LintCode::MissingIcon, "actions.example.icon"),
+                (LintCode::MissingInspector, "actions.example.inspector"),
+            ]
+        );
+        assert_eq!(lints[0].severity, Severity::Warning);
+        assert_eq!(lints[3].severity, Severity::Info);
+    }
+
+    #[test]
+    fn test_lint_icon_pack() {
+        let manifest = IconsManifest::parse(
+            r#"{ "manifest_version": 2, "icons": { "id": "com.example.pack", "name": "Pack", "version": "0.1.0", "authors": ["Example"] } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            codes(&manifest.lint()),
+            [
+                (LintCode::MissingDescription, "icons.description"),
+                (LintCode::MissingIcon, "icons.icon"),
+                (LintCode::EmptyIconList, "list"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_unknown_field() {
+        let source = r#"{ "manifest_version": 2, "icons": { "id": "com.example.pack", "name": "Pack", "version": "0.1.0", "authors": [], "descripton": "Pack" } }"#;
+        let lints = IconsManifest::lint_source(source, ManifestFormat::Json).unwrap();
+
+        assert_eq!(
+            codes(&lints),
+            [
+                (LintCode::UnknownField, "icons.descripton"),
+                (LintCode::MissingDescription, "icons.description"),
+                (LintCode::MissingIcon, "icons.icon"),
+                (LintCode::MissingAuthors, "icons.authors"),
+                (LintCode::EmptyIconList, "list"),
+            ]
+        );
+        assert_eq!(
+            lints[0].message,
+            "unknown field \"descripton\", did you mean \"description\"?"
+        );
+    }
+
+    #[test]
+    fn test_lint_source_invalid() {
+        let source = "[plugin]\nid = 1\n";
+        assert!(PluginManifest::lint_source(source, ManifestFormat::Toml).is_err());
+    }
+
+    #[test]
+    fn test_lint_code_display() {
+        assert_eq!(LintCode::MissingInspector.to_string(), "missing-inspector");
+        assert_eq!(
+            serde_json::to_string(&LintCode::LongLabel).unwrap(),
+            "\"long-label\""
+        );
+    }
+}
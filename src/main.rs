@@ -0,0 +1,660 @@
+//! # Tilepad Manifest CLI
+//!
+//! Command line tool for validating, linting and formatting manifests
+//! and generating the manifest JSON schemas
+//!
+//! Exit codes: 0 on success, 1 when a manifest is invalid, has warnings
+//! or needs formatting and 2 when the command could not be run
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use indexmap::IndexMap;
+use schemars::Schema;
+use serde::Serialize;
+use std::{
+    fmt::Display,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+use tilepad_manifest::{
+    ManifestError,
+    bundle::validate_bundle,
+    diagnostic::Diagnostic,
+    form::InspectorForm,
+    format::ManifestFormat,
+    icons::IconsManifest,
+    lint::{Lint, Severity},
+    plugin::PluginManifest,
+    scaffold::{IconPackScaffold, NativeTarget, PluginScaffold, ScaffoldBinary},
+    schema::{SchemaDraft, icons_schema_with, plugin_schema_with},
+    typescript::{icons_typescript, plugin_typescript},
+    validation::ValidationOptions,
+};
+
+#[derive(Parser)]
+#[command(name = "tilepad-manifest", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Validate a manifest
+    Validate {
+        #[command(flatten)]
+        manifest: ManifestArgs,
+
+        /// Reject unknown fields rather than ignoring them
+        #[arg(long)]
+        deny_unknown_fields: bool,
+
+        /// Check the files referenced by the manifest exist relative
+        /// to the manifest directory
+        #[arg(long)]
+        bundle: bool,
+    },
+
+    /// Check a manifest for non-fatal quality issues, fails
+    /// when any warnings are found
+    Lint {
+        #[command(flatten)]
+        manifest: ManifestArgs,
+    },
+
+    /// Generate the manifest JSON schemas
+    Schema {
+        /// Directory to write the schemas to, the schema is written
+        /// to stdout when not specified
+        #[arg(long)]
+        out: Option<PathBuf>,
+
+        /// Kind of manifest to generate the schema for, required when
+        /// writing to stdout, defaults to all kinds when writing to a directory
+        #[arg(long, value_enum)]
+        kind: Option<ManifestKind>,
+
+        /// JSON schema draft to generate (draft-07, 2020-12)
+        #[arg(long, default_value_t)]
+        draft: SchemaDraft,
+    },
+
+    /// Generate TypeScript definitions (.d.ts) for the manifests
+    Types {
+        /// Directory to write the definitions to, the definitions are
+        /// written to stdout when not specified
+        #[arg(long)]
+        out: Option<PathBuf>,
+
+        /// Kind of manifest to generate definitions for, required when
+        /// writing to stdout, defaults to all kinds when writing to a directory
+        #[arg(long, value_enum)]
+        kind: Option<ManifestKind>,
+    },
+
+    /// Print the inspector forms generated from the declared action
+    /// settings as JSON
+    Form {
+        /// Path to the plugin manifest file
+        path: PathBuf,
+
+        /// Format of the manifest (json, json5, toml), detected from the
+        /// file extension when not specified
+        #[arg(long)]
+        format: Option<ManifestFormat>,
+
+        /// Only print the form for the action with this ID
+        #[arg(long)]
+        action: Option<String>,
+    },
+
+    /// Create a new plugin or icon pack project
+    New(NewArgs),
+
+    /// Format a manifest (comments are not preserved)
+    Fmt {
+        #[command(flatten)]
+        manifest: ManifestArgs,
+
+        /// Check whether the manifest is formatted without writing changes
+        #[arg(long)]
+        check: bool,
+    },
+}
+
+/// Arguments for commands operating on a manifest file
+#[derive(Args)]
+struct ManifestArgs {
+    /// Path to the manifest file
+    path: PathBuf,
+
+    /// Kind of manifest, detected from the contents when not specified
+    #[arg(long, value_enum)]
+    kind: Option<ManifestKind>,
+
+    /// Format of the manifest (json, json5, toml), detected from the
+    /// file extension when not specified
+    #[arg(long)]
+    format: Option<ManifestFormat>,
+
+    /// Output results as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+/// Arguments for creating a new project
+#[derive(Args)]
+struct NewArgs {
+    /// Directory to create the project in
+    dir: PathBuf,
+
+    /// Kind of project to create
+    #[arg(long, value_enum, default_value_t = ManifestKind::Plugin)]
+    kind: ManifestKind,
+
+    /// Unique ID for the project (i.e com.example.my-plugin)
+    #[arg(long)]
+    id: String,
+
+    /// Name of the project
+    #[arg(long)]
+    name: String,
+
+    /// Author of the project, can be specified multiple times
+    #[arg(long = "author")]
+    authors: Vec<String>,
+
+    /// Description of the project
+    #[arg(long)]
+    description: Option<String>,
+
+    /// Native binary target (i.e windows-x64), can be specified multiple
+    /// times, plugins use a node entrypoint when no targets are specified
+    #[arg(long = "native")]
+    native: Vec<NativeTarget>,
+
+    /// Format to write the manifest in (json, toml)
+    #[arg(long, default_value_t)]
+    format: ManifestFormat,
+}
+
+/// Kind of manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum ManifestKind {
+    /// Plugin manifest
+    Plugin,
+    /// Icon pack manifest
+    Icons,
+}
+
+impl ManifestKind {
+    /// File name for generated files for the kind, without an extension
+    fn file_stem(&self) -> &'static str {
+        match self {
+            ManifestKind::Plugin => "plugins",
+            ManifestKind::Icons => "icons",
+        }
+    }
+
+    /// Generate the JSON schema for the kind
+    fn schema(&self, draft: SchemaDraft) -> Schema {
+        match self {
+            ManifestKind::Plugin => plugin_schema_with(draft),
+            ManifestKind::Icons => icons_schema_with(draft),
+        }
+    }
+
+    /// Generate the TypeScript definitions for the kind
+    fn typescript(&self) -> String {
+        match self {
+            ManifestKind::Plugin => plugin_typescript(),
+            ManifestKind::Icons => icons_typescript(),
+        }
+    }
+}
+
+/// Parsed manifest of either kind
+enum Manifest {
+    Plugin(Box<PluginManifest>),
+    Icons(Box<IconsManifest>),
+}
+
+impl Manifest {
+    fn validate_bundle(&self, root_dir: &Path) -> Result<(), garde::Report> {
+        match self {
+            Manifest::Plugin(manifest) => validate_bundle(manifest.as_ref(), root_dir),
+            Manifest::Icons(manifest) => validate_bundle(manifest.as_ref(), root_dir),
+        }
+    }
+}
+
+/// Manifest source loaded from a file
+struct Source {
+    path: PathBuf,
+    text: String,
+    format: ManifestFormat,
+    kind: ManifestKind,
+}
+
+impl Source {
+    fn load(args: &ManifestArgs) -> Result<Source, String> {
+        let text = std::fs::read_to_string(&args.path)
+            .map_err(|error| format!("failed to read {}: {error}", args.path.display()))?;
+
+        let format = args
+            .format
+            .or_else(|| ManifestFormat::from_path(&args.path))
+            .unwrap_or_default();
+
+        let kind = match args.kind {
+            Some(kind) => kind,
+            // Icon packs are identified by their "icons" section
+            None => match format.deserialize::<serde_json::Value>(&text) {
+                Ok(value) if value.get("icons").is_some() => ManifestKind::Icons,
+                _ => ManifestKind::Plugin,
+            },
+        };
+
+        Ok(Source {
+            path: args.path.clone(),
+            text,
+            format,
+            kind,
+        })
+    }
+
+    fn parse(&self, options: &ValidationOptions) -> Result<Manifest, ManifestError> {
+        Ok(match self.kind {
+            ManifestKind::Plugin => {
+                let manifest = PluginManifest::parse_as(&self.text, self.format, options)?;
+                Manifest::Plugin(Box::new(manifest))
+            }
+            ManifestKind::Icons => {
+                let manifest = IconsManifest::parse_as(&self.text, self.format, options)?;
+                Manifest::Icons(Box::new(manifest))
+            }
+        })
+    }
+
+    fn lint(&self) -> Result<Vec<Lint>, ManifestError> {
+        match self.kind {
+            ManifestKind::Plugin => PluginManifest::lint_source(&self.text, self.format),
+            ManifestKind::Icons => IconsManifest::lint_source(&self.text, self.format),
+        }
+    }
+
+    /// Print the diagnostics for the source in a human readable format
+    fn print_diagnostics(&self, diagnostics: &[Diagnostic]) {
+        for diagnostic in diagnostics {
+            let location = match &diagnostic.span {
+                Some(span) => format!(
+                    "{}:{}:{}",
+                    self.path.display(),
+                    span.start.line,
+                    span.start.column
+                ),
+                None => self.path.display().to_string(),
+            };
+
+            match &diagnostic.path {
+                Some(path) => eprintln!("{location}: error: {path}: {}", diagnostic.message),
+                None => eprintln!("{location}: error: {}", diagnostic.message),
+            }
+        }
+    }
+}
+
+/// JSON output for commands operating on a manifest
+#[derive(Serialize)]
+struct Report<'a> {
+    path: &'a Path,
+    kind: ManifestKind,
+    valid: bool,
+    diagnostics: Vec<Diagnostic>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lints: Option<Vec<Lint>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    formatted: Option<bool>,
+}
+
+impl<'a> Report<'a> {
+    fn new(source: &'a Source, diagnostics: Vec<Diagnostic>) -> Self {
+        Self {
+            path: &source.path,
+            kind: source.kind,
+            valid: diagnostics.is_empty(),
+            diagnostics,
+            lints: None,
+            formatted: None,
+        }
+    }
+
+    fn print(&self) -> Result<(), CliError> {
+        let output = serde_json::to_string_pretty(self).map_err(|error| error.to_string())?;
+        print(output)
+    }
+}
+
+/// Error that prevented a command from running
+enum CliError {
+    /// Stdout was closed before all the output was written (i.e piped
+    /// into `head`), not treated as a failure
+    BrokenPipe,
+    /// Error message to report
+    Message(String),
+}
+
+impl From<String> for CliError {
+    fn from(value: String) -> Self {
+        CliError::Message(value)
+    }
+}
+
+impl From<&str> for CliError {
+    fn from(value: &str) -> Self {
+        CliError::Message(value.to_string())
+    }
+}
+
+/// Write a line of `output` to stdout
+fn print(output: impl Display) -> Result<(), CliError> {
+    writeln!(std::io::stdout().lock(), "{output}").map_err(|error| match error.kind() {
+        ErrorKind::BrokenPipe => CliError::BrokenPipe,
+        _ => CliError::Message(format!("failed to write output: {error}")),
+    })
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli.command) {
+        Ok(true) | Err(CliError::BrokenPipe) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(CliError::Message(error)) => {
+            eprintln!("error: {error}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Run the command, returns whether the command succeeded
+fn run(command: Command) -> Result<bool, CliError> {
+    match command {
+        Command::Validate {
+            manifest,
+            deny_unknown_fields,
+            bundle,
+        } => validate(&manifest, deny_unknown_fields, bundle),
+        Command::Lint { manifest } => lint(&manifest),
+        Command::Schema { out, kind, draft } => generate(out.as_deref(), kind, "json", |kind| {
+            serde_json::to_string_pretty(&kind.schema(draft)).map_err(|error| error.to_string())
+        }),
+        Command::Types { out, kind } => {
+            generate(out.as_deref(), kind, "d.ts", |kind| Ok(kind.typescript()))
+        }
+        Command::Form {
+            path,
+            format,
+            action,
+        } => form(
+            &ManifestArgs {
+                path,
+                kind: Some(ManifestKind::Plugin),
+                format,
+                json: false,
+            },
+            action.as_deref(),
+        ),
+        Command::New(args) => new(args),
+        Command::Fmt { manifest, check } => fmt(&manifest, check),
+    }
+}
+
+fn validate(
+    args: &ManifestArgs,
+    deny_unknown_fields: bool,
+    bundle: bool,
+) -> Result<bool, CliError> {
+    let source = Source::load(args)?;
+    let options = ValidationOptions {
+        deny_unknown_fields,
+        ..Default::default()
+    };
+
+    let diagnostics = match source.parse(&options) {
+        Ok(manifest) if bundle => {
+            let root_dir = source.path.parent().unwrap_or(Path::new("."));
+            match manifest.validate_bundle(root_dir) {
+                Ok(()) => Vec::new(),
+                Err(report) => {
+                    ManifestError::Validation(report).diagnostics(&source.text, source.format)
+                }
+            }
+        }
+        Ok(_) => Vec::new(),
+        Err(error) => error.diagnostics(&source.text, source.format),
+    };
+
+    let report = Report::new(&source, diagnostics);
+    if args.json {
+        report.print()?;
+    } else if report.valid {
+        print(format_args!("{}: manifest is valid", source.path.display()))?;
+    } else {
+        source.print_diagnostics(&report.diagnostics);
+    }
+
+    Ok(report.valid)
+}
+
+fn lint(args: &ManifestArgs) -> Result<bool, CliError> {
+    let source = Source::load(args)?;
+
+    let (diagnostics, lints) = match source.lint() {
+        Ok(lints) => (Vec::new(), lints),
+        Err(error) => (error.diagnostics(&source.text, source.format), Vec::new()),
+    };
+
+    let success =
+        diagnostics.is_empty() && !lints.iter().any(|lint| lint.severity == Severity::Warning);
+
+    if args.json {
+        let mut report = Report::new(&source, diagnostics);
+        report.lints = Some(lints);
+        report.print()?;
+    } else {
+        source.print_diagnostics(&diagnostics);
+        for lint in &lints {
+            eprintln!(
+                "{}: {}[{}]: {}: {}",
+                source.path.display(),
+                lint.severity,
+                lint.code,
+                lint.path,
+                lint.message
+            );
+        }
+    }
+
+    Ok(success)
+}
+
+fn form(args: &ManifestArgs, action: Option<&str>) -> Result<bool, CliError> {
+    let source = Source::load(args)?;
+
+    let manifest = match source.parse(&ValidationOptions::default()) {
+        Ok(Manifest::Plugin(manifest)) => manifest,
+        Ok(Manifest::Icons(_)) => return Err("forms are only available for plugins".into()),
+        Err(error) => {
+            source.print_diagnostics(&error.diagnostics(&source.text, source.format));
+            return Ok(false);
+        }
+    };
+
+    let output = match action {
+        Some(action) => {
+            let form = manifest
+                .actions
+                .0
+                .iter()
+                .find(|(id, _)| id.as_str() == action)
+                .ok_or_else(|| format!("unknown action \"{action}\""))?
+                .1
+                .form()
+                .ok_or_else(|| format!("action \"{action}\" does not have a generated form"))?;
+
+            serde_json::to_string_pretty(&form)
+        }
+        None => {
+            let forms: IndexMap<&str, InspectorForm> = manifest
+                .actions
+                .0
+                .iter()
+                .filter_map(|(id, action)| Some((id.as_str(), action.form()?)))
+                .collect();
+
+            serde_json::to_string_pretty(&forms)
+        }
+    }
+    .map_err(|error| error.to_string())?;
+
+    print(output)?;
+    Ok(true)
+}
+
+/// Generate output for each manifest kind using `generate`, writes to
+/// stdout when no `out` directory is provided
+fn generate<F>(
+    out: Option<&Path>,
+    kind: Option<ManifestKind>,
+    extension: &str,
+    generate: F,
+) -> Result<bool, CliError>
+where
+    F: Fn(ManifestKind) -> Result<String, String>,
+{
+    let Some(out) = out else {
+        let kind = kind.ok_or("--kind is required when writing to stdout")?;
+        print(generate(kind)?)?;
+        return Ok(true);
+    };
+
+    std::fs::create_dir_all(out)
+        .map_err(|error| format!("failed to create {}: {error}", out.display()))?;
+
+    let kinds = match kind {
+        Some(kind) => vec![kind],
+        None => vec![ManifestKind::Plugin, ManifestKind::Icons],
+    };
+
+    for kind in kinds {
+        let path = out.join(format!("{}.{extension}", kind.file_stem()));
+        std::fs::write(&path, generate(kind)?)
+            .map_err(|error| format!("failed to write {}: {error}", path.display()))?;
+    }
+
+    Ok(true)
+}
+
+fn new(args: NewArgs) -> Result<bool, CliError> {
+    let NewArgs {
+        dir,
+        kind,
+        id,
+        name,
+        authors,
+        description,
+        native,
+        format,
+    } = args;
+
+    if format == ManifestFormat::Json5 {
+        return Err("new manifests can only be written as json or toml".into());
+    }
+
+    let binary = if native.is_empty() {
+        ScaffoldBinary::Node
+    } else {
+        ScaffoldBinary::Native(native)
+    };
+
+    let invalid_id = |error: garde::Report| format!("invalid id \"{id}\": {error}");
+    let written = match kind {
+        ManifestKind::Plugin => PluginScaffold {
+            id: id.parse().map_err(invalid_id)?,
+            name,
+            authors,
+            description,
+            binary,
+        }
+        .build()
+        .map_err(|error| error.to_string())?
+        .write(&dir, format),
+        ManifestKind::Icons => IconPackScaffold {
+            id: id.parse().map_err(invalid_id)?,
+            name,
+            authors,
+            description,
+        }
+        .build()
+        .map_err(|error| error.to_string())?
+        .write(&dir, format),
+    }
+    .map_err(|error| error.to_string())?;
+
+    for path in written {
+        print(format_args!("created {}", path.display()))?;
+    }
+
+    Ok(true)
+}
+
+fn fmt(args: &ManifestArgs, check: bool) -> Result<bool, CliError> {
+    let source = Source::load(args)?;
+
+    // The source value is formatted rather than the parsed manifest so that
+    // only the layout changes and defaulted fields are not written out
+    let reformat: fn(&str) -> Result<String, ManifestError> = match source.format {
+        ManifestFormat::Json => |text| {
+            let value: serde_json::Value = ManifestFormat::Json.deserialize(text)?;
+            ManifestFormat::Json.serialize(&value)
+        },
+        ManifestFormat::Toml => |text| {
+            let value: toml::Table = ManifestFormat::Toml.deserialize(text)?;
+            ManifestFormat::Toml.serialize(&value)
+        },
+        ManifestFormat::Json5 => return Err("JSON5 manifests cannot be formatted".into()),
+    };
+
+    if let Err(error) = source.parse(&ValidationOptions::default()) {
+        let report = Report::new(&source, error.diagnostics(&source.text, source.format));
+        if args.json {
+            report.print()?;
+        } else {
+            source.print_diagnostics(&report.diagnostics);
+        }
+        return Ok(false);
+    }
+
+    let mut formatted = reformat(&source.text).map_err(|error| error.to_string())?;
+    if !formatted.ends_with('\n') {
+        formatted.push('\n');
+    }
+
+    let is_formatted = formatted == source.text;
+
+    if !check && !is_formatted {
+        std::fs::write(&source.path, &formatted)
+            .map_err(|error| format!("failed to write {}: {error}", source.path.display()))?;
+    }
+
+    if args.json {
+        let mut report = Report::new(&source, Vec::new());
+        report.formatted = Some(is_formatted);
+        report.print()?;
+    } else if check && !is_formatted {
+        eprintln!("{}: manifest is not formatted", source.path.display());
+    }
+
+    Ok(!check || is_formatted)
+}
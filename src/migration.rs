@@ -0,0 +1,353 @@
+//! # Migration
+//!
+//! Upgrades manifests written for older versions of the manifest structure
+//! to the current structure. Migrations operate on the raw manifest value
+//! before it is deserialized, so manifests that would no longer parse can
+//! still be loaded
+//!
+//! Manifests that do not specify a `manifest_version` are treated as
+//! version 1, the structure used before the field was introduced. The
+//! field is required when parsing without migrating
+
+use garde::Path;
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+
+/// Current version of the manifest structure
+pub const CURRENT_MANIFEST_VERSION: u32 = 2;
+
+/// Version assumed for manifests that do not specify a `manifest_version`
+pub const LEGACY_MANIFEST_VERSION: u32 = 1;
+
+/// Errors that can occur when migrating a manifest
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    /// Manifest was not an object
+    #[error("manifest must be an object")]
+    NotObject,
+    /// Manifest version was not a positive integer
+    #[error("manifest_version must be a positive integer")]
+    InvalidVersion,
+    /// Manifest was written for a newer version than is supported
+    #[error(
+        "manifest version {0} is newer than the latest supported version {CURRENT_MANIFEST_VERSION}"
+    )]
+    UnsupportedVersion(u64),
+}
+
+/// Single change made while migrating a manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationChange {
+    /// Path to the field that was changed (i.e plugin.version)
+    pub path: String,
+    /// Message describing the change
+    pub message: String,
+}
+
+/// Report of the changes made while migrating a manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationReport {
+    /// Version the manifest was written for
+    pub from_version: u32,
+    /// Version the manifest was migrated to
+    pub to_version: u32,
+    /// Changes made to the manifest
+    pub changes: Vec<MigrationChange>,
+}
+
+impl MigrationReport {
+    /// Whether the manifest was already using the current version
+    pub fn is_up_to_date(&self) -> bool {
+        self.from_version == self.to_version
+    }
+}
+
+/// Migration from one version of a manifest to the next
+struct Migration {
+    /// Version the migration upgrades from, upgrades to the next version
+    from: u32,
+    /// Function applying the migration
+    apply: fn(&mut Migrator<'_>),
+}
+
+/// Migrations for plugin manifests
+static PLUGIN_MIGRATIONS: [Migration; 1] = [Migration {
+    from: 1,
+    apply: plugin_v1_to_v2,
+}];
+
+/// Migrations for icon pack manifests
+static ICONS_MIGRATIONS: [Migration; 1] = [Migration {
+    from: 1,
+    apply: icons_v1_to_v2,
+}];
+
+/// Migrate a plugin manifest `value` to the current version
+pub fn migrate_plugin(value: &mut Value) -> Result<MigrationReport, MigrationError> {
+    migrate(value, &PLUGIN_MIGRATIONS)
+}
+
+/// Migrate an icon pack manifest `value` to the current version
+pub fn migrate_icons(value: &mut Value) -> Result<MigrationReport, MigrationError> {
+    migrate(value, &ICONS_MIGRATIONS)
+}
+
+/// Apply the `migrations` required to bring `value` up to the current version
+fn migrate(value: &mut Value, migrations: &[Migration]) -> Result<MigrationReport, MigrationError> {
+    let object = value.as_object_mut().ok_or(MigrationError::NotObject)?;
+    let from_version = match object.get("manifest_version") {
+        Some(version) => {
+            let version = version.as_u64().ok_or(MigrationError::InvalidVersion)?;
+            if version > CURRENT_MANIFEST_VERSION as u64 {
+                return Err(MigrationError::UnsupportedVersion(version));
+            }
+
+            u32::try_from(version)
+                .ok()
+                .filter(|version| *version >= LEGACY_MANIFEST_VERSION)
+                .ok_or(MigrationError::InvalidVersion)?
+        }
+        None => LEGACY_MANIFEST_VERSION,
+    };
+
+    let mut migrator = Migrator {
+        value,
+        changes: Vec::new(),
+    };
+
+    for version in from_version..CURRENT_MANIFEST_VERSION {
+        if let Some(migration) = migrations
+            .iter()
+            .find(|migration| migration.from == version)
+        {
+            (migration.apply)(&mut migrator);
+        }
+    }
+
+    if from_version != CURRENT_MANIFEST_VERSION {
+        migrator.value["manifest_version"] = CURRENT_MANIFEST_VERSION.into();
+        migrator.change(
+            Path::new("manifest_version"),
+            format!("updated manifest version from {from_version} to {CURRENT_MANIFEST_VERSION}"),
+        );
+    }
+
+    Ok(MigrationReport {
+        from_version,
+        to_version: CURRENT_MANIFEST_VERSION,
+        changes: migrator.changes,
+    })
+}
+
+/// State for a migration in progress
+struct Migrator<'a> {
+    /// Manifest value being migrated
+    value: &'a mut Value,
+    /// Changes made so far
+    changes: Vec<MigrationChange>,
+}
+
+impl Migrator<'_> {
+    /// Record a change
+    fn change(&mut self, path: Path, message: String) {
+        self.changes.push(MigrationChange {
+            path: path.to_string(),
+            message,
+        });
+    }
+
+    /// Get a mutable reference to the value at `path`
+    fn get_mut(&mut self, path: &[&str]) -> Option<&mut Value> {
+        path.iter()
+            .try_fold(&mut *self.value, |value, key| value.get_mut(*key))
+    }
+
+    /// Complete a partial version (i.e 1.0) into a full semver version
+    fn normalize_version(&mut self, path: &[&str]) {
+        let Some(Value::String(version)) = self.get_mut(path) else {
+            return;
+        };
+
+        let parts: Vec<&str> = version.split('.').collect();
+        let numeric = parts
+            .iter()
+            .all(|part| !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit()));
+        if !numeric || parts.len() >= 3 {
+            return;
+        }
+
+        let previous = version.clone();
+        for _ in parts.len()..3 {
+            version.push_str(".0");
+        }
+
+        let message = format!("expanded version \"{previous}\" to \"{version}\"");
+        self.change(to_path(path), message);
+    }
+
+    /// Replace backslash separators within the path at `path`
+    fn normalize_path(&mut self, path: &[&str]) {
+        let Some(value) = self.get_mut(path) else {
+            return;
+        };
+
+        if let Some(message) = normalize_separators(value) {
+            self.change(to_path(path), message);
+        }
+    }
+
+    /// Replace backslash separators within the path field `field` of
+    /// each item in the array at `path`
+    fn normalize_array_paths(&mut self, path: &[&str], field: &str) {
+        let Some(Value::Array(items)) = self.get_mut(path) else {
+            return;
+        };
+
+        let messages: Vec<(usize, String)> = items
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                let value = item.get_mut(field)?;
+                normalize_separators(value).map(|message| (index, message))
+            })
+            .collect();
+
+        for (index, message) in messages {
+            self.change(to_path(path).join(index).join(field), message);
+        }
+    }
+}
+
+/// Replace backslash separators within a path value
+fn normalize_separators(value: &mut Value) -> Option<String> {
+    let Value::String(path) = value else {
+        return None;
+    };
+
+    if !path.contains('\\') {
+        return None;
+    }
+
+    let previous = std::mem::replace(path, path.replace('\\', "/"));
+    Some(format!("replaced separators in \"{previous}\" with \"/\""))
+}
+
+/// Convert a list of keys into a [Path]
+fn to_path(keys: &[&str]) -> Path {
+    keys.iter().fold(Path::empty(), |path, key| path.join(*key))
+}
+
+/// Version 2 requires full semver versions and forward slash path separators
+fn plugin_v1_to_v2(migrator: &mut Migrator<'_>) {
+    migrator.normalize_version(&["plugin", "version"]);
+    migrator.normalize_path(&["plugin", "icon"]);
+    migrator.normalize_path(&["category", "icon"]);
+    migrator.normalize_path(&["bin", "node", "entrypoint"]);
+    migrator.normalize_array_paths(&["bin", "native"], "path");
+
+    let action_ids: Vec<String> = migrator
+        .get_mut(&["actions"])
+        .and_then(|actions| actions.as_object())
+        .map(|actions| actions.keys().cloned().collect())
+        .unwrap_or_default();
+
+    for action_id in action_ids {
+        for field in ["icon", "display", "inspector"] {
+            migrator.normalize_path(&["actions", &action_id, field]);
+        }
+    }
+}
+
+/// Version 2 requires full semver versions and forward slash path separators
+fn icons_v1_to_v2(migrator: &mut Migrator<'_>) {
+    migrator.normalize_version(&["icons", "version"]);
+    migrator.normalize_path(&["icons", "icon"]);
+    migrator.normalize_array_paths(&["list"], "path");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_migrate_legacy_plugin() {
+        let mut value = json!({
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "1.2", "authors": [] },
+            "bin": { "node": { "entrypoint": "bin\\index.js" } },
+            "category": { "label": "Test" },
+            "actions": {
+                "example": { "label": "Example", "inspector": "inspector\\index.html" }
+            }
+        });
+
+        let report = migrate_plugin(&mut value).unwrap();
+        assert_eq!(report.from_version, 1);
+        assert_eq!(report.to_version, CURRENT_MANIFEST_VERSION);
+
+        let paths: Vec<&str> = report
+            .changes
+            .iter()
+            .map(|change| change.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "plugin.version",
+                "bin.node.entrypoint",
+                "actions.example.inspector",
+                "manifest_version"
+            ]
+        );
+
+        assert_eq!(value["plugin"]["version"], "1.2.0");
+        assert_eq!(value["bin"]["node"]["entrypoint"], "bin/index.js");
+        assert_eq!(value["manifest_version"], CURRENT_MANIFEST_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_current_plugin_unchanged() {
+        let mut value = json!({
+            "manifest_version": CURRENT_MANIFEST_VERSION,
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "1.2", "authors": [] },
+        });
+        let original = value.clone();
+
+        let report = migrate_plugin(&mut value).unwrap();
+        assert!(report.is_up_to_date());
+        assert!(report.changes.is_empty());
+        assert_eq!(value, original);
+    }
+
+    #[test]
+    fn test_migrate_legacy_icons() {
+        let mut value = json!({
+            "icons": { "id": "com.example.pack", "name": "Pack", "version": "1", "authors": [] },
+            "list": [
+                { "path": "icons\\arrow.svg", "name": "Arrow", "keywords": ["direction"] }
+            ]
+        });
+
+        let report = migrate_icons(&mut value).unwrap();
+        assert_eq!(report.changes.len(), 3);
+        assert_eq!(value["icons"]["version"], "1.0.0");
+        assert_eq!(value["list"][0]["path"], "icons/arrow.svg");
+        assert_eq!(value["list"][0]["keywords"], json!(["direction"]));
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_version() {
+        let mut value = json!({ "manifest_version": CURRENT_MANIFEST_VERSION + 1 });
+        assert!(matches!(
+            migrate_plugin(&mut value),
+            Err(MigrationError::UnsupportedVersion(_))
+        ));
+
+        let mut value = json!({ "manifest_version": "2" });
+        assert!(matches!(
+            migrate_plugin(&mut value),
+            Err(MigrationError::InvalidVersion)
+        ));
+    }
+}
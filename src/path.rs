@@ -0,0 +1,124 @@
+//! # Path
+//!
+//! Relative paths to files within a plugin or icon pack bundle
+
+use crate::validation::validate_path;
+use garde::Validate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Path to a file within the plugin or icon pack bundle, relative
+/// to the bundle root directory
+///
+/// Must use forward slashes as separators (i.e images/icon.svg) and
+/// must not be absolute or escape the bundle root using ".."
+#[derive(
+    Debug, Clone, Serialize, Deserialize, Validate, Hash, PartialEq, Eq, PartialOrd, Ord, JsonSchema,
+)]
+#[garde(transparent)]
+#[serde(transparent)]
+#[schemars(example = &"images/icon.svg")]
+pub struct ManifestPath(#[garde(custom(validate_path))] pub String);
+
+impl ManifestPath {
+    /// Get the inner path as a [str] slice
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Get the file extension of the path if one is present
+    pub fn extension(&self) -> Option<&str> {
+        let file_name = self.0.rsplit('/').next().unwrap_or(&self.0);
+        file_name
+            .rsplit_once('.')
+            .map(|(_, extension)| extension)
+            .filter(|extension| !extension.is_empty())
+    }
+
+    /// Resolve the path relative to the provided `base_dir`
+    ///
+    /// The path is validated before resolving so it cannot lexically escape
+    /// the `base_dir` (i.e using ".." or an absolute path), the filesystem is
+    /// not accessed so symlinks within the `base_dir` may still point outside it
+    pub fn resolve(&self, base_dir: &Path) -> Result<PathBuf, garde::Report> {
+        self.validate()?;
+
+        let mut path = base_dir.to_path_buf();
+        for segment in self.0.split('/').filter(|segment| *segment != ".") {
+            path.push(segment);
+        }
+
+        Ok(path)
+    }
+}
+
+impl TryFrom<String> for ManifestPath {
+    type Error = garde::Report;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::from_str(&value)
+    }
+}
+
+impl FromStr for ManifestPath {
+    type Err = garde::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = ManifestPath(s.to_string());
+        value.validate()?;
+        Ok(value)
+    }
+}
+
+impl Display for ManifestPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl AsRef<str> for ManifestPath {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_joins_segments() {
+        let path: ManifestPath = "./inspector/index.html".parse().unwrap();
+        let resolved = path.resolve(Path::new("/plugins/example")).unwrap();
+        assert_eq!(
+            resolved,
+            Path::new("/plugins/example")
+                .join("inspector")
+                .join("index.html")
+        );
+    }
+
+    #[test]
+    fn test_resolve_rejects_escaping_path() {
+        // Constructed directly to bypass validation on creation
+        let path = ManifestPath("../../etc/passwd".to_string());
+        assert!(path.resolve(Path::new("/plugins/example")).is_err());
+    }
+
+    #[test]
+    fn test_extension() {
+        let path: ManifestPath = "images/icon.svg".parse().unwrap();
+        assert_eq!(path.extension(), Some("svg"));
+
+        let path: ManifestPath = "display/clock.display.html".parse().unwrap();
+        assert_eq!(path.extension(), Some("html"));
+
+        let path: ManifestPath = "bin.d/program".parse().unwrap();
+        assert_eq!(path.extension(), None);
+    }
+}
@@ -0,0 +1,344 @@
+//! # Permissions
+//!
+//! Capabilities plugins declare they need, allows hosts to show users
+//! what a plugin accesses before it is installed
+
+use crate::validation::{ValidationOptions, validate_network_host};
+use garde::Validate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::fmt::Display;
+use strum::{Display, EnumString};
+
+/// Permissions requested by a plugin
+#[skip_serializing_none]
+#[derive(Debug, Default, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[garde(context(ValidationOptions))]
+#[serde(default)]
+pub struct Permissions {
+    /// Hosts the plugin connects to
+    #[garde(dive(()))]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub network: Vec<NetworkHost>,
+
+    /// Locations on the filesystem the plugin accesses
+    #[garde(dive)]
+    #[serde(skip_serializing_if = "FilesystemPermissions::is_empty")]
+    pub filesystem: FilesystemPermissions,
+
+    /// Access the plugin needs to the clipboard
+    #[garde(skip)]
+    pub clipboard: Option<Access>,
+
+    /// Whether the plugin runs shell commands or other programs
+    #[garde(skip)]
+    pub shell: Option<bool>,
+
+    /// Whether the plugin shows system notifications
+    #[garde(skip)]
+    pub notifications: Option<bool>,
+
+    /// Whether the plugin simulates keyboard and mouse input
+    #[garde(skip)]
+    pub input: Option<bool>,
+}
+
+impl Permissions {
+    /// Whether no permissions are requested
+    pub fn is_empty(&self) -> bool {
+        self.summary().is_empty()
+    }
+
+    /// Human readable summary of the requested permissions, one line per
+    /// permission, for showing to users when installing the plugin
+    pub fn summary(&self) -> Vec<String> {
+        let mut summary = Vec::new();
+
+        if !self.network.is_empty() {
+            if self.network.iter().any(NetworkHost::is_any) {
+                summary.push("Connect to any host on the network".to_string());
+            } else {
+                let hosts = self
+                    .network
+                    .iter()
+                    .map(NetworkHost::describe)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                summary.push(format!("Connect to {hosts}"));
+            }
+        }
+
+        for permission in &self.filesystem.0 {
+            summary.push(format!(
+                "{} files in {}",
+                permission.access.verb(),
+                permission.scope.describe()
+            ));
+        }
+
+        if let Some(access) = self.clipboard {
+            summary.push(
+                match access {
+                    Access::Read => "Read your clipboard",
+                    Access::Write => "Write to your clipboard",
+                    Access::ReadWrite => "Read and write to your clipboard",
+                }
+                .to_string(),
+            );
+        }
+
+        if self.shell.unwrap_or_default() {
+            summary.push("Run shell commands and other programs".to_string());
+        }
+
+        if self.notifications.unwrap_or_default() {
+            summary.push("Show notifications".to_string());
+        }
+
+        if self.input.unwrap_or_default() {
+            summary.push("Simulate keyboard and mouse input".to_string());
+        }
+
+        summary
+    }
+}
+
+/// Host a plugin connects to, optionally including a port
+/// (i.e api.example.com, localhost:8080)
+///
+/// Subdomains can be matched using a leading wildcard (i.e *.example.com)
+/// and any host can be matched using "*"
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, Hash, PartialEq, Eq, JsonSchema)]
+#[garde(transparent)]
+#[serde(transparent)]
+#[schemars(example = &"api.example.com")]
+pub struct NetworkHost(#[garde(custom(validate_network_host))] pub String);
+
+impl NetworkHost {
+    /// Get the inner host as a [str] slice
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Whether the host matches any host
+    pub fn is_any(&self) -> bool {
+        self.0 == "*"
+    }
+
+    /// Describe the host for the permission summary
+    fn describe(&self) -> String {
+        match self.0.strip_prefix("*.") {
+            Some(domain) => format!("any subdomain of {domain}"),
+            None => self.0.clone(),
+        }
+    }
+}
+
+impl Display for NetworkHost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Locations on the filesystem a plugin accesses
+///
+/// Each scope can only be requested once
+#[derive(Debug, Default, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct FilesystemPermissions(pub Vec<FilesystemPermission>);
+
+impl FilesystemPermissions {
+    /// Whether no locations are requested
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl AsRef<[FilesystemPermission]> for FilesystemPermissions {
+    fn as_ref(&self) -> &[FilesystemPermission] {
+        &self.0
+    }
+}
+
+/// Access to a location on the filesystem
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct FilesystemPermission {
+    /// Location being accessed
+    pub scope: FilesystemScope,
+
+    /// Access needed to the location
+    #[serde(default)]
+    pub access: Access,
+}
+
+/// Location on the filesystem a plugin can access
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, EnumString, Display, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum FilesystemScope {
+    /// User documents folder
+    Documents,
+    /// User downloads folder
+    Downloads,
+    /// User desktop folder
+    Desktop,
+    /// User pictures folder
+    Pictures,
+    /// User music folder
+    Music,
+    /// User videos folder
+    Videos,
+    /// User home folder, includes all the other user folders
+    Home,
+    /// Temporary files folder
+    Temp,
+}
+
+impl FilesystemScope {
+    /// Describe the scope for the permission summary
+    fn describe(&self) -> &'static str {
+        match self {
+            FilesystemScope::Documents => "your Documents folder",
+            FilesystemScope::Downloads => "your Downloads folder",
+            FilesystemScope::Desktop => "your Desktop folder",
+            FilesystemScope::Pictures => "your Pictures folder",
+            FilesystemScope::Music => "your Music folder",
+            FilesystemScope::Videos => "your Videos folder",
+            FilesystemScope::Home => "your home folder",
+            FilesystemScope::Temp => "the temporary files folder",
+        }
+    }
+}
+
+/// Level of access to a resource
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumString,
+    Display,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Access {
+    /// Read only access
+    #[default]
+    Read,
+    /// Write only access
+    Write,
+    /// Read and write access
+    ReadWrite,
+}
+
+impl Access {
+    /// Verb describing the access for the permission summary
+    fn verb(&self) -> &'static str {
+        match self {
+            Access::Read => "Read",
+            Access::Write => "Write",
+            Access::ReadWrite => "Read and write",
+        }
+    }
+
+    /// Whether the access allows reading
+    pub fn can_read(&self) -> bool {
+        matches!(self, Access::Read | Access::ReadWrite)
+    }
+
+    /// Whether the access allows writing
+    pub fn can_write(&self) -> bool {
+        matches!(self, Access::Write | Access::ReadWrite)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permissions_summary() {
+        let permissions: Permissions = serde_json::from_str(
+            r#"{
+                "network": ["api.example.com", "*.example.org", "localhost:8080"],
+                "filesystem": [
+                    { "scope": "documents" },
+                    { "scope": "downloads", "access": "read_write" }
+                ],
+                "clipboard": "write",
+                "shell": true,
+                "notifications": true,
+                "input": false
+            }"#,
+        )
+        .unwrap();
+
+        assert!(permissions.validate_with(&Default::default()).is_ok());
+        assert_eq!(
+            permissions.summary(),
+            [
+                "Connect to api.example.com, any subdomain of example.org, localhost:8080",
+                "Read files in your Documents folder",
+                "Read and write files in your Downloads folder",
+                "Write to your clipboard",
+                "Run shell commands and other programs",
+                "Show notifications",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_permissions_summary_any_host() {
+        let permissions = Permissions {
+            network: vec![
+                NetworkHost("api.example.com".to_string()),
+                NetworkHost("*".to_string()),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            permissions.summary(),
+            ["Connect to any host on the network"]
+        );
+        assert!(Permissions::default().is_empty());
+    }
+
+    #[test]
+    fn test_permissions_validation() {
+        let permissions: Permissions = serde_json::from_str(
+            r#"{
+                "network": ["https://example.com", "example.com:99999", "a.*.com", "-bad.com"],
+                "filesystem": [{ "scope": "documents" }, { "scope": "documents", "access": "write" }]
+            }"#,
+        )
+        .unwrap();
+
+        let report = permissions.validate_with(&Default::default()).unwrap_err();
+        let paths: Vec<String> = report.iter().map(|(path, _)| path.to_string()).collect();
+        assert_eq!(
+            paths,
+            [
+                "filesystem[1]",
+                "network[0]",
+                "network[1]",
+                "network[2]",
+                "network[3]"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_access() {
+        assert!(Access::ReadWrite.can_read() && Access::ReadWrite.can_write());
+        assert!(!Access::Read.can_write());
+        assert_eq!(Access::ReadWrite.to_string(), "read_write");
+    }
+}
@@ -4,15 +4,32 @@
 
 use crate::{
     ManifestError,
+    color::Color,
+    engines::{Engines, Incompatibility},
+    form::InspectorForm,
+    format::ManifestFormat,
+    migration::{MigrationReport, migrate_plugin},
+    path::ManifestPath,
+    permissions::Permissions,
+    settings::SettingsSchema,
     system::{Arch, OperatingSystem, platform_arch, platform_os},
-    validation::{validate_color, validate_id, validate_name},
+    unknown::unknown_fields,
+    validation::{
+        ValidationOptions, validate_action_settings, validate_binary, validate_id,
+        validate_internal, validate_manifest_version, validate_name, validate_text,
+    },
+    version::ManifestVersion,
 };
-use garde::Validate;
+use garde::{Report, Validate};
 use indexmap::IndexMap;
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{
+    Deserialize, Deserializer, Serialize,
+    de::{self, MapAccess, Visitor},
+};
 use serde_with::skip_serializing_none;
 use std::{fmt::Display, str::FromStr};
+use strum::Display;
 
 /// Unique ID for a plugin
 ///
@@ -75,14 +92,24 @@ impl AsRef<node_semver::Range> for BinaryNodeVersion {
 /// Manifest file format for plugins
 #[skip_serializing_none]
 #[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[garde(context(ValidationOptions))]
 pub struct PluginManifest {
+    /// Version of the manifest structure, manifests without a version are
+    /// legacy manifests that must be parsed with migration
+    #[garde(custom(validate_manifest_version))]
+    pub manifest_version: u32,
+
     /// Details about the plugin itself
     #[garde(dive)]
     pub plugin: MPlugin,
 
+    /// Versions of Tilepad the plugin supports
+    #[garde(skip)]
+    pub engines: Option<Engines>,
+
     /// Details for running the plugin
     /// (Option not specified for internal plugins)
-    #[garde(dive)]
+    #[garde(dive, custom(validate_binary))]
     pub bin: Option<MBin>,
 
     /// Category for the manifest actions
@@ -92,77 +119,227 @@ pub struct PluginManifest {
     /// Map of available plugin actions
     #[garde(dive)]
     pub actions: ActionMap,
+
+    /// Plugin wide settings shared by all actions (i.e API keys,
+    /// server addresses), configured separately from the tiles
+    #[garde(dive)]
+    pub settings: Option<SettingsSchema>,
+
+    /// Permissions the plugin needs, shown to users when
+    /// installing the plugin
+    #[garde(dive)]
+    pub permissions: Option<Permissions>,
 }
 
 impl TryFrom<&str> for PluginManifest {
     type Error = ManifestError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let manifest: PluginManifest = serde_json::from_str(value)?;
-        manifest.validate()?;
-        Ok(manifest)
+        Self::parse_with(value, &ValidationOptions::default())
     }
 }
 
 impl TryFrom<&[u8]> for PluginManifest {
     type Error = ManifestError;
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        let manifest: PluginManifest = serde_json::from_slice(value)?;
-        manifest.validate()?;
-        Ok(manifest)
+        Self::parse_with(std::str::from_utf8(value)?, &ValidationOptions::default())
     }
 }
 
 impl PluginManifest {
-    /// Parse a plugin manifest from a string
+    /// Parse a plugin manifest from a string using the
+    /// default [ValidationOptions]
     #[inline]
     pub fn parse(value: &str) -> Result<PluginManifest, ManifestError> {
         Self::try_from(value)
     }
+
+    /// Parse a plugin manifest from a string validating
+    /// using the provided `options`
+    pub fn parse_with(
+        value: &str,
+        options: &ValidationOptions,
+    ) -> Result<PluginManifest, ManifestError> {
+        Self::parse_as(value, ManifestFormat::Json, options)
+    }
+
+    /// Parse a plugin manifest from a string in the provided `format`
+    /// validating using the provided `options`
+    pub fn parse_as(
+        value: &str,
+        format: ManifestFormat,
+        options: &ValidationOptions,
+    ) -> Result<PluginManifest, ManifestError> {
+        let manifest: PluginManifest = format.deserialize(value)?;
+        let mut report = match manifest.validate_with(options) {
+            Ok(()) => Report::new(),
+            Err(report) => report,
+        };
+
+        if options.deny_unknown_fields {
+            for field in unknown_fields::<PluginManifest>(value, format)? {
+                report.append(field.path.clone(), garde::Error::new(field.to_string()));
+            }
+        }
+
+        if !report.is_empty() {
+            return Err(report.into());
+        }
+
+        Ok(manifest)
+    }
+
+    /// Parse a plugin manifest from a string in the provided `format`, upgrading
+    /// manifests written for older versions of the manifest structure
+    /// before validating using the provided `options`
+    ///
+    /// Diagnostics for errors are relative to the migrated manifest
+    /// rather than the original `value`
+    pub fn parse_migrated(
+        value: &str,
+        format: ManifestFormat,
+        options: &ValidationOptions,
+    ) -> Result<(PluginManifest, MigrationReport), ManifestError> {
+        let mut value: serde_json::Value = format.deserialize(value)?;
+        let report = migrate_plugin(&mut value)?;
+        let value = serde_json::to_string(&value)?;
+        let manifest = Self::parse_as(&value, ManifestFormat::Json, options)?;
+        Ok((manifest, report))
+    }
+
+    /// Serialize the manifest into a string in the provided `format`
+    pub fn to_string_as(&self, format: ManifestFormat) -> Result<String, ManifestError> {
+        format.serialize(self)
+    }
+
+    /// Validate the stored plugin `settings` against the declared plugin
+    /// settings, any settings are accepted when the plugin does not
+    /// declare its settings
+    pub fn validate_settings(
+        &self,
+        settings: &serde_json::Value,
+        options: &ValidationOptions,
+    ) -> Result<(), Report> {
+        match &self.settings {
+            Some(schema) => schema.validate_settings(settings, options),
+            None => Ok(()),
+        }
+    }
+
+    /// Get the form for configuring the plugin settings, only present
+    /// when the plugin declares its settings
+    pub fn settings_form(&self) -> Option<InspectorForm> {
+        self.settings.as_ref().map(InspectorForm::from)
+    }
+
+    /// Check whether the `host_version` of Tilepad is supported by the
+    /// plugin, see [Engines::is_compatible_with]
+    pub fn is_compatible_with(
+        &self,
+        host_version: &node_semver::Version,
+    ) -> Result<(), Incompatibility> {
+        match &self.engines {
+            Some(engines) => engines.is_compatible_with(host_version),
+            None => Ok(()),
+        }
+    }
 }
 
 /// Plugin details section of the manifest
 #[skip_serializing_none]
 #[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[garde(context(ValidationOptions))]
+// "internal" is skipped from the schema, declared so its not reported as unknown
+#[schemars(extend("x-undocumented" = ["internal"]))]
 pub struct MPlugin {
     /// Unique ID of the plugin (e.g com.jacobtread.tilepad.obs)
-    #[garde(dive)]
+    #[garde(dive(()))]
     #[schemars(example = "com.jacobtread.tilepad.obs")]
     pub id: PluginId,
     /// Name of the plugin
-    #[garde(length(min = 1))]
+    #[garde(length(min = 1), custom(validate_text))]
     #[schemars(example = "Example Plugin")]
     pub name: String,
     /// Current version of the plugin, semver compatible version number
-    #[garde(length(min = 1))]
-    #[schemars(example = "0.1.0")]
-    pub version: String,
+    #[garde(dive)]
+    pub version: ManifestVersion,
     /// List of authors for the plugin
-    #[garde(inner(length(min = 1)))]
+    #[garde(inner(length(min = 1), custom(validate_text)))]
     #[schemars(example = ["Example Author 1", "Example Author 2"])]
     pub authors: Vec<String>,
     /// Description of what the plugin does
-    #[garde(skip)]
+    #[garde(inner(custom(validate_text)))]
     #[schemars(example = "My plugin that performs my actions")]
     pub description: Option<String>,
     /// Icon for the plugin
-    #[garde(skip)]
+    #[garde(dive(()))]
     #[schemars(example = "images/icon.svg")]
-    pub icon: Option<String>,
+    pub icon: Option<ManifestPath>,
     /// Internal Field - Determines whether the plugin is "internal" to tilepad and
     /// bundled (Prevents uninstalling and some other features)
-    #[garde(skip)]
+    #[garde(custom(validate_internal))]
     #[schemars(skip)]
     pub internal: Option<bool>,
 }
 
+impl MPlugin {
+    /// Check if this plugin is a newer version than `other`
+    pub fn is_newer_than(&self, other: &MPlugin) -> bool {
+        self.version > other.version
+    }
+}
+
 /// Ordered map of actions defined within the plugin
 ///
 /// Keys must be unique to each action following
 /// the [a-zA-Z_-] format (i.e example_action, my-action, MyAction)
-#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+///
+/// Duplicate keys are rejected when deserializing rather than
+/// overwriting the previous action
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct ActionMap(pub IndexMap<ActionId, ManifestAction>);
 
+impl<'de> Deserialize<'de> for ActionMap {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ActionMapVisitor;
+
+        impl<'de> Visitor<'de> for ActionMapVisitor {
+            type Value = ActionMap;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a map of actions")
+            }
+
+            fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut actions = IndexMap::with_capacity(access.size_hint().unwrap_or_default());
+
+                while let Some(key) = access.next_key::<ActionId>()? {
+                    if let Some(index) = actions.get_index_of(&key) {
+                        return Err(de::Error::custom(format!(
+                            "duplicate action key \"{key}\" (defined as action #{} and action #{})",
+                            index + 1,
+                            actions.len() + 1
+                        )));
+                    }
+
+                    let action: ManifestAction = access.next_value()?;
+                    actions.insert(key, action);
+                }
+
+                Ok(ActionMap(actions))
+            }
+        }
+
+        deserializer.deserialize_map(ActionMapVisitor)
+    }
+}
+
 impl AsRef<IndexMap<ActionId, ManifestAction>> for ActionMap {
     fn as_ref(&self) -> &IndexMap<ActionId, ManifestAction> {
         &self.0
@@ -172,15 +349,16 @@ impl AsRef<IndexMap<ActionId, ManifestAction>> for ActionMap {
 /// Definition of the category to place the plugin actions within
 #[skip_serializing_none]
 #[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[garde(context(ValidationOptions))]
 pub struct MCategory {
     /// Label for the category in the actions sidebar
-    #[garde(length(min = 1))]
+    #[garde(length(min = 1), custom(validate_text))]
     #[schemars(example = "My Category")]
     pub label: String,
     /// Icon to show in the actions sidebar
-    #[garde(skip)]
+    #[garde(dive(()))]
     #[schemars(example = "images/icon.svg")]
-    pub icon: Option<String>,
+    pub icon: Option<ManifestPath>,
 }
 
 /// Name of an action
@@ -231,23 +409,24 @@ impl Display for ActionId {
 /// Manifest action definition
 #[skip_serializing_none]
 #[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[garde(context(ValidationOptions))]
 pub struct ManifestAction {
     /// Label for the action, shown in the sidebar
-    #[garde(length(min = 1))]
+    #[garde(length(min = 1), custom(validate_text))]
     #[schemars(example = "My Action")]
     pub label: String,
 
     /// Icon for the action, shown in the sidebar and
     /// used as the default icon when added to the grid
-    #[garde(skip)]
+    #[garde(dive(()))]
     #[schemars(example = "images/icon.svg")]
-    pub icon: Option<String>,
+    pub icon: Option<ManifestPath>,
 
     /// Path to a "display" HTML file that can be used to make
     /// the tile into a display tile
-    #[garde(skip)]
+    #[garde(dive(()))]
     #[schemars(example = "display/my-display.display.html")]
-    pub display: Option<String>,
+    pub display: Option<ManifestPath>,
 
     /// Default options for the icon when added to the grid
     /// as a tile
@@ -256,19 +435,55 @@ pub struct ManifestAction {
 
     /// Description for the action, shown as a tooltip when hovering
     /// the action
-    #[garde(skip)]
+    #[garde(inner(custom(validate_text)))]
     #[schemars(example = "My action")]
     pub description: Option<String>,
 
     /// Path to the inspector HTML file to use for configuring the action
-    #[garde(skip)]
+    ///
+    /// When not specified the inspector is generated from the
+    /// declared settings
+    #[garde(dive(()))]
     #[schemars(example = "inspector/index.html")]
-    pub inspector: Option<String>,
+    pub inspector: Option<ManifestPath>,
+
+    /// Settings stored by the action, used to validate and fill
+    /// in defaults for the settings of tiles using the action
+    #[garde(dive, custom(validate_action_settings))]
+    pub settings: Option<SettingsSchema>,
+}
+
+impl ManifestAction {
+    /// Get the form to render as the inspector for the action, only
+    /// present when the action declares its settings and does not
+    /// provide its own inspector HTML
+    pub fn form(&self) -> Option<InspectorForm> {
+        if self.inspector.is_some() {
+            return None;
+        }
+
+        self.settings.as_ref().map(InspectorForm::from)
+    }
+
+    /// Validate the stored `settings` for a tile using the action against
+    /// the declared settings, any settings are accepted when the action
+    /// does not declare its settings
+    pub fn validate_settings(
+        &self,
+        settings: &serde_json::Value,
+        options: &ValidationOptions,
+    ) -> Result<(), Report> {
+        match &self.settings {
+            Some(schema) => schema.validate_settings(settings, options),
+            None => Ok(()),
+        }
+    }
 }
 
 /// Default options for an action icon
 #[skip_serializing_none]
 #[derive(Default, Debug, Clone, Serialize, Deserialize, Validate, JsonSchema)]
+#[garde(context(ValidationOptions))]
 #[serde(default)]
 pub struct ManifestActionIconOptions {
     /// Padding in pixels to pad the icon with
@@ -276,18 +491,17 @@ pub struct ManifestActionIconOptions {
     pub padding: Option<u32>,
 
     /// Color for the tile background behind the icon
-    #[garde(inner(custom(validate_color)))]
-    #[schemars(example = "#ffffff")]
-    pub background_color: Option<String>,
+    #[garde(dive)]
+    pub background_color: Option<Color>,
 
     /// Color of the tile border
-    #[garde(inner(custom(validate_color)))]
-    #[schemars(example = "#ffffff")]
-    pub border_color: Option<String>,
+    #[garde(dive)]
+    pub border_color: Option<Color>,
 }
 
 /// Type of binary the plugin program is using
 #[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[garde(context(ValidationOptions))]
 #[serde(untagged)]
 pub enum MBin {
     /// Program uses the node runtime
@@ -303,16 +517,39 @@ pub enum MBin {
     },
 }
 
+impl MBin {
+    /// Get the kind of binary
+    pub fn kind(&self) -> BinaryKind {
+        match self {
+            MBin::Node { .. } => BinaryKind::Node,
+            MBin::Native { .. } => BinaryKind::Native,
+        }
+    }
+}
+
+/// Kind of binary a plugin program uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BinaryKind {
+    /// Node runtime
+    #[strum(serialize = "node")]
+    Node,
+    /// Native binary
+    #[strum(serialize = "native")]
+    Native,
+}
+
 /// Node "binary" which uses a node runtime to execute the js script
 /// at the provided `entrypoint`
 #[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[garde(context(ValidationOptions))]
 pub struct MBinNode {
     /// Entrypoint for the program
     ///
     /// e.g bin/index.js
-    #[garde(length(min = 1))]
+    #[garde(dive(()))]
     #[schemars(example = "bin/index.js")]
-    pub entrypoint: String,
+    pub entrypoint: ManifestPath,
 
     /// Version of node the program should run using.
     ///
@@ -325,7 +562,7 @@ pub struct MBinNode {
 }
 
 /// Default node version to use
-fn default_node_version() -> BinaryNodeVersion {
+pub(crate) fn default_node_version() -> BinaryNodeVersion {
     BinaryNodeVersion(
         node_semver::Range::parse("=22.18.0").expect("hardcoded range should be valid"),
     )
@@ -334,6 +571,7 @@ fn default_node_version() -> BinaryNodeVersion {
 /// Native binary for a specific os + arch combo, contains a
 /// path to the binary
 #[derive(Debug, Clone, Deserialize, Serialize, Validate, JsonSchema)]
+#[garde(context(ValidationOptions))]
 pub struct MBinNative {
     // Target OS this binary should be used for
     #[garde(skip)]
@@ -344,9 +582,9 @@ pub struct MBinNative {
     pub arch: Arch,
 
     /// Path to the executable file
-    #[garde(length(min = 1))]
+    #[garde(dive(()))]
     #[schemars(example = "bin/example.exe")]
-    pub path: String,
+    pub path: ManifestPath,
 }
 
 impl MBinNative {
@@ -374,13 +612,512 @@ impl MBinNative {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        color::ColorMode,
+        form::FormInput,
+        migration::{CURRENT_MANIFEST_VERSION, LEGACY_MANIFEST_VERSION},
+        settings::SettingKind,
+    };
+
+    #[test]
+    fn test_action_map_rejects_duplicate_keys() {
+        let source = r#"{
+            "first": { "label": "First" },
+            "second": { "label": "Second" },
+            "first": { "label": "First Again" }
+        }"#;
+
+        let error = serde_json::from_str::<ActionMap>(source).unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("duplicate action key \"first\""));
+        assert!(message.contains("action #1 and action #3"));
+    }
+
+    #[test]
+    fn test_action_map_preserves_order() {
+        let source = r#"{
+            "b": { "label": "B" },
+            "a": { "label": "A" }
+        }"#;
+
+        let actions = serde_json::from_str::<ActionMap>(source).unwrap();
+        let keys: Vec<&str> = actions.0.keys().map(ActionId::as_str).collect();
+        assert_eq!(keys, ["b", "a"]);
+    }
+
+    #[test]
+    fn test_parse_toml_manifest() {
+        let source = r##"
+            manifest_version = 2
+
+            [plugin]
+            id = "com.example.test"
+            name = "Test"
+            version = "0.1.0"
+            authors = ["Example"]
+
+            [bin.node]
+            entrypoint = "bin/index.js"
+
+            [category]
+            label = "Test"
+
+            [actions.second]
+            label = "Second"
+            icon_options = { background_color = "#ff0000" }
+
+            [actions.first]
+            label = "First"
+        "##;
+
+        let options = ValidationOptions::default();
+        let manifest = PluginManifest::parse_as(source, ManifestFormat::Toml, &options).unwrap();
+        let keys: Vec<&str> = manifest.actions.0.keys().map(ActionId::as_str).collect();
+        assert_eq!(keys, ["second", "first"]);
+
+        // Round trip through both formats
+        let toml = manifest.to_string_as(ManifestFormat::Toml).unwrap();
+        let manifest = PluginManifest::parse_as(&toml, ManifestFormat::Toml, &options).unwrap();
+        let json = manifest.to_string_as(ManifestFormat::Json).unwrap();
+        let manifest = PluginManifest::parse(&json).unwrap();
+        assert_eq!(manifest.plugin.id.as_str(), "com.example.test");
+        assert!(matches!(manifest.bin, Some(MBin::Node { .. })));
+    }
+
+    #[test]
+    fn test_parse_action_settings() {
+        let source = r#"
+            manifest_version = 2
+
+            [plugin]
+            id = "com.example.test"
+            name = "Test"
+            version = "0.1.0"
+            authors = []
+
+            [category]
+            label = "Test"
+
+            [actions.counter]
+            label = "Counter"
+
+            [actions.counter.settings.step]
+            type = "number"
+            label = "Step"
+            min = 1
+            default = 1
+
+            [actions.counter.settings.mode]
+            type = "enum"
+            options = [{ value = "up" }, { value = "down" }]
+        "#;
+
+        let options = ValidationOptions::default();
+        let manifest = PluginManifest::parse_as(source, ManifestFormat::Toml, &options).unwrap();
+        let action = &manifest.actions.0[0];
+
+        assert!(
+            action
+                .validate_settings(&serde_json::json!({ "step": 2, "mode": "up" }), &options)
+                .is_ok()
+        );
+        assert!(
+            action
+                .validate_settings(&serde_json::json!({ "step": 0 }), &options)
+                .is_err()
+        );
+
+        // Round trip keeps the flattened setting types
+        let toml = manifest.to_string_as(ManifestFormat::Toml).unwrap();
+        let manifest = PluginManifest::parse_as(&toml, ManifestFormat::Toml, &options).unwrap();
+        let settings = manifest.actions.0[0].settings.as_ref().unwrap();
+        assert!(matches!(settings.0[1].kind, SettingKind::Enum { .. }));
+    }
+
+    #[test]
+    fn test_plugin_settings() {
+        let source = r#"{
+            "manifest_version": 2,
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "0.1.0", "authors": [] },
+            "category": { "label": "Test" },
+            "actions": {},
+            "settings": {
+                "server": { "type": "string", "label": "Server", "default": "localhost" },
+                "api_key": { "type": "string", "label": "API Key", "secret": true, "required": true }
+            }
+        }"#;
+
+        let manifest = PluginManifest::parse(source).unwrap();
+        let options = ValidationOptions::default();
+
+        assert!(
+            manifest
+                .validate_settings(&serde_json::json!({ "api_key": "secret" }), &options)
+                .is_ok()
+        );
+        assert!(
+            manifest
+                .validate_settings(&serde_json::json!({ "server": "remote" }), &options)
+                .is_err()
+        );
+
+        let form = manifest.settings_form().unwrap();
+        assert!(matches!(form.fields[0].input, FormInput::Text { .. }));
+        assert!(matches!(form.fields[1].input, FormInput::Password { .. }));
+    }
+
+    #[test]
+    fn test_action_settings_reject_secrets() {
+        let source = r#"{
+            "manifest_version": 2,
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "0.1.0", "authors": [] },
+            "category": { "label": "Test" },
+            "actions": {
+                "example": {
+                    "label": "Example",
+                    "settings": { "token": { "type": "string", "secret": true } }
+                }
+            }
+        }"#;
+
+        let Err(ManifestError::Validation(report)) = PluginManifest::parse(source) else {
+            panic!("expected validation error");
+        };
+        let (path, error) = report.iter().next().unwrap();
+        assert_eq!(path.to_string(), "actions.example.settings");
+        assert!(error.to_string().contains("\"token\" is secret"));
+    }
+
+    #[test]
+    fn test_engines_compatibility() {
+        let source = r#"
+            manifest_version = 2
+
+            [plugin]
+            id = "com.example.test"
+            name = "Test"
+            version = "0.1.0"
+            authors = []
+
+            [engines]
+            tilepad = "^0.2.0"
+
+            [category]
+            label = "Test"
+
+            [actions]
+        "#;
+
+        let manifest =
+            PluginManifest::parse_as(source, ManifestFormat::Toml, &Default::default()).unwrap();
+
+        let host = node_semver::Version::parse("0.2.3").unwrap();
+        assert!(manifest.is_compatible_with(&host).is_ok());
+
+        let host = node_semver::Version::parse("0.3.0").unwrap();
+        assert!(matches!(
+            manifest.is_compatible_with(&host),
+            Err(Incompatibility::HostTooNew { .. })
+        ));
+    }
+
+    #[test]
+    fn test_action_form() {
+        let mut action: ManifestAction = serde_json::from_str(
+            r#"{
+                "label": "Counter",
+                "settings": { "step": { "type": "number", "label": "Step" } }
+            }"#,
+        )
+        .unwrap();
+
+        let form = action.form().unwrap();
+        assert_eq!(form.fields.len(), 1);
+        assert_eq!(form.fields[0].label, "Step");
+
+        // Actions with their own inspector don't use a generated form
+        action.inspector = Some(ManifestPath("inspector/index.html".to_string()));
+        assert!(action.form().is_none());
+    }
+
+    #[test]
+    fn test_parse_json5_manifest() {
+        let source = r#"{
+            // Comments and trailing commas are allowed
+            manifest_version: 2,
+            plugin: {
+                id: "com.example.test",
+                name: 'Test',
+                version: "0.1.0",
+                authors: ["Example",],
+            },
+            category: { label: "Test" },
+            actions: {
+                example: { label: "Example" }, /* Inline comment */
+            },
+        }"#;
+
+        let options = ValidationOptions::default();
+        let manifest = PluginManifest::parse_as(source, ManifestFormat::Json5, &options).unwrap();
+        assert_eq!(manifest.plugin.name, "Test");
+        assert_eq!(manifest.actions.0.len(), 1);
+
+        // Strict JSON parsing must still reject the relaxed syntax
+        assert!(matches!(
+            PluginManifest::parse(source),
+            Err(ManifestError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn test_deny_unknown_fields() {
+        let source = r#"{
+            "manifest_version": 2,
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "0.1.0", "authors": [] },
+            "category": { "label": "Test" },
+            "actions": {
+                "example": { "label": "Example", "inspecter": "inspector/index.html" }
+            }
+        }"#;
+
+        // Ignored by default
+        assert!(PluginManifest::parse(source).is_ok());
+
+        let options = ValidationOptions {
+            deny_unknown_fields: true,
+            ..Default::default()
+        };
+        let error = PluginManifest::parse_with(source, &options).unwrap_err();
+        let ManifestError::Validation(report) = error else {
+            panic!("expected validation error");
+        };
+
+        let (path, error) = report.iter().next().unwrap();
+        assert_eq!(path.to_string(), "actions.example.inspecter");
+        assert_eq!(
+            error.message(),
+            "unknown field \"inspecter\", did you mean \"inspector\"?"
+        );
+    }
+
+    #[test]
+    fn test_parse_migrated_legacy_manifest() {
+        let source = r#"{
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "1.0", "authors": [] },
+            "bin": { "node": { "entrypoint": "bin\\index.js" } },
+            "category": { "label": "Test" },
+            "actions": {}
+        }"#;
+
+        // Legacy manifest no longer parses directly
+        assert!(PluginManifest::parse(source).is_err());
+
+        let (manifest, report) =
+            PluginManifest::parse_migrated(source, ManifestFormat::Json, &Default::default())
+                .unwrap();
+        assert_eq!(manifest.manifest_version, CURRENT_MANIFEST_VERSION);
+        assert_eq!(manifest.plugin.version, ManifestVersion::new(1, 0, 0));
+        assert_eq!(report.from_version, 1);
+        assert_eq!(report.changes.len(), 3);
+    }
+
+    #[test]
+    fn test_missing_manifest_version_is_legacy() {
+        let source = r#"{
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "1.0.0", "authors": [] },
+            "category": { "label": "Test" },
+            "actions": {}
+        }"#;
+
+        let Err(ManifestError::Json(error)) = PluginManifest::parse(source) else {
+            panic!("expected missing manifest_version error");
+        };
+        assert!(
+            error
+                .to_string()
+                .contains("missing field `manifest_version`")
+        );
+
+        let (manifest, report) =
+            PluginManifest::parse_migrated(source, ManifestFormat::Json, &Default::default())
+                .unwrap();
+        assert_eq!(report.from_version, LEGACY_MANIFEST_VERSION);
+        assert_eq!(manifest.manifest_version, CURRENT_MANIFEST_VERSION);
+    }
+
+    #[test]
+    fn test_loose_version_reported() {
+        let source = r#"{
+            "manifest_version": 2,
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "v1.0.0", "authors": [] },
+            "category": { "label": "Test" },
+            "actions": {}
+        }"#;
+
+        let Err(ManifestError::Validation(report)) = PluginManifest::parse(source) else {
+            panic!("expected validation error");
+        };
+        let paths: Vec<String> = report.iter().map(|(path, _)| path.to_string()).collect();
+        assert_eq!(paths, ["plugin.version"]);
+    }
+
+    #[test]
+    fn test_rejects_outdated_manifest_version() {
+        let source = r#"{
+            "manifest_version": 1,
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "1.0.0", "authors": [] },
+            "category": { "label": "Test" },
+            "actions": {}
+        }"#;
+
+        let Err(ManifestError::Validation(report)) = PluginManifest::parse(source) else {
+            panic!("expected validation error");
+        };
+        let (path, _) = report.iter().next().unwrap();
+        assert_eq!(path.to_string(), "manifest_version");
+
+        let (manifest, _) =
+            PluginManifest::parse_migrated(source, ManifestFormat::Json, &Default::default())
+                .unwrap();
+        assert_eq!(manifest.manifest_version, CURRENT_MANIFEST_VERSION);
+    }
+
+    #[test]
+    fn test_rejects_newer_manifest_version() {
+        let source = r#"{
+            "manifest_version": 100,
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "1.0.0", "authors": [] },
+            "category": { "label": "Test" },
+            "actions": {}
+        }"#;
+
+        assert!(matches!(
+            PluginManifest::parse(source),
+            Err(ManifestError::Validation(_))
+        ));
+        assert!(matches!(
+            PluginManifest::parse_migrated(source, ManifestFormat::Json, &Default::default()),
+            Err(ManifestError::Migration(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_toml_validates() {
+        let source = r#"
+            manifest_version = 2
+
+            [plugin]
+            id = "com.example.test"
+            name = ""
+            version = "0.1.0"
+            authors = []
+
+            [category]
+            label = "Test"
+
+            [actions]
+        "#;
+
+        let error = PluginManifest::parse_as(source, ManifestFormat::Toml, &Default::default())
+            .unwrap_err();
+        assert!(matches!(error, ManifestError::Validation(_)));
+    }
+
+    #[test]
+    fn test_named_colors_depend_on_color_mode() {
+        let source = r#"{
+            "manifest_version": 2,
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "0.1.0", "authors": [] },
+            "category": { "label": "Test" },
+            "actions": {
+                "example": {
+                    "label": "Example",
+                    "icon_options": { "background_color": "white" }
+                }
+            }
+        }"#;
+
+        assert!(PluginManifest::parse(source).is_err());
+
+        let options = ValidationOptions {
+            color_mode: ColorMode::Lenient,
+            ..Default::default()
+        };
+        assert!(PluginManifest::parse_with(source, &options).is_ok());
+    }
+
+    #[test]
+    fn test_max_text_length_option() {
+        let source = r#"{
+            "manifest_version": 2,
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "0.1.0", "authors": [] },
+            "category": { "label": "Test" },
+            "actions": {
+                "example": { "label": "A very long action label" }
+            }
+        }"#;
+
+        assert!(PluginManifest::parse(source).is_ok());
+
+        let options = ValidationOptions {
+            max_text_length: Some(10),
+            ..Default::default()
+        };
+        let error = PluginManifest::parse_with(source, &options).unwrap_err();
+        let ManifestError::Validation(report) = error else {
+            panic!("expected validation error");
+        };
+        let (path, _) = report.iter().next().unwrap();
+        assert_eq!(path.to_string(), "actions.example.label");
+    }
+
+    #[test]
+    fn test_allowed_binaries_option() {
+        let source = r#"{
+            "manifest_version": 2,
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "0.1.0", "authors": [] },
+            "bin": { "node": { "entrypoint": "bin/index.js" } },
+            "category": { "label": "Test" },
+            "actions": {}
+        }"#;
+
+        let options = ValidationOptions {
+            allowed_binaries: vec![BinaryKind::Native],
+            ..Default::default()
+        };
+        assert!(PluginManifest::parse_with(source, &options).is_err());
+
+        let options = ValidationOptions {
+            allowed_binaries: vec![BinaryKind::Node],
+            ..Default::default()
+        };
+        assert!(PluginManifest::parse_with(source, &options).is_ok());
+    }
+
+    #[test]
+    fn test_allow_internal_option() {
+        let source = r#"{
+            "manifest_version": 2,
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "0.1.0", "authors": [], "internal": true },
+            "category": { "label": "Test" },
+            "actions": {}
+        }"#;
+
+        assert!(PluginManifest::parse(source).is_ok());
+
+        let options = ValidationOptions {
+            allow_internal: false,
+            ..Default::default()
+        };
+        assert!(PluginManifest::parse_with(source, &options).is_err());
+    }
 
     #[test]
     fn test_is_usable_true_when_matches() {
         let bin = MBinNative {
             os: OperatingSystem::Linux,
             arch: Arch::X64,
-            path: "bin/linux-x64".to_string(),
+            path: "bin/linux-x64".parse().unwrap(),
         };
         assert!(bin.is_usable(&OperatingSystem::Linux, &Arch::X64));
     }
@@ -390,7 +1127,7 @@ mod tests {
         let bin = MBinNative {
             os: OperatingSystem::Linux,
             arch: Arch::X64,
-            path: "bin/linux-x64".to_string(),
+            path: "bin/linux-x64".parse().unwrap(),
         };
         assert!(!bin.is_usable(&OperatingSystem::Windows, &Arch::X64));
     }
@@ -400,7 +1137,7 @@ mod tests {
         let bin = MBinNative {
             os: OperatingSystem::Linux,
             arch: Arch::X64,
-            path: "bin/linux-x64".to_string(),
+            path: "bin/linux-x64".parse().unwrap(),
         };
         assert!(!bin.is_usable(&OperatingSystem::Linux, &Arch::X86));
     }
@@ -411,17 +1148,17 @@ mod tests {
             MBinNative {
                 os: OperatingSystem::Windows,
                 arch: Arch::X64,
-                path: "bin/win-x64".to_string(),
+                path: "bin/win-x64".parse().unwrap(),
             },
             MBinNative {
                 os: OperatingSystem::Linux,
                 arch: Arch::X64,
-                path: "bin/linux-x64".to_string(),
+                path: "bin/linux-x64".parse().unwrap(),
             },
         ];
         let result = MBinNative::find_usable(&bins, &OperatingSystem::Linux, &Arch::X64);
         assert!(result.is_some());
-        assert_eq!(result.unwrap().path, "bin/linux-x64");
+        assert_eq!(result.unwrap().path.as_str(), "bin/linux-x64");
     }
 
     #[test]
@@ -430,12 +1167,12 @@ mod tests {
             MBinNative {
                 os: OperatingSystem::Windows,
                 arch: Arch::X64,
-                path: "bin/win-x64".to_string(),
+                path: "bin/win-x64".parse().unwrap(),
             },
             MBinNative {
                 os: OperatingSystem::MacOs,
                 arch: Arch::Arm64,
-                path: "bin/macos-arm64".to_string(),
+                path: "bin/macos-arm64".parse().unwrap(),
             },
         ];
         let result = MBinNative::find_usable(&bins, &OperatingSystem::Linux, &Arch::X64);
@@ -448,16 +1185,16 @@ mod tests {
             MBinNative {
                 os: OperatingSystem::Linux,
                 arch: Arch::X64,
-                path: "bin/linux-x64-v1".to_string(),
+                path: "bin/linux-x64-v1".parse().unwrap(),
             },
             MBinNative {
                 os: OperatingSystem::Linux,
                 arch: Arch::X64,
-                path: "bin/linux-x64-v2".to_string(),
+                path: "bin/linux-x64-v2".parse().unwrap(),
             },
         ];
         let result = MBinNative::find_usable(&bins, &OperatingSystem::Linux, &Arch::X64);
         assert!(result.is_some());
-        assert_eq!(result.unwrap().path, "bin/linux-x64-v1");
+        assert_eq!(result.unwrap().path.as_str(), "bin/linux-x64-v1");
     }
 }
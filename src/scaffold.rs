@@ -0,0 +1,484 @@
+//! # Scaffold
+//!
+//! Generates the manifest and directory skeleton for new plugin and
+//! icon pack projects
+
+use crate::{
+    ManifestError,
+    format::ManifestFormat,
+    icons::{Icon, IconList, IconPackId, IconsManifest, MIconPack},
+    migration::CURRENT_MANIFEST_VERSION,
+    path::ManifestPath,
+    plugin::{
+        ActionId, ActionMap, MBin, MBinNative, MBinNode, MCategory, MPlugin, ManifestAction,
+        PluginId, PluginManifest, default_node_version,
+    },
+    system::{Arch, OperatingSystem},
+    validation::ValidationOptions,
+    version::ManifestVersion,
+};
+use garde::Validate;
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::{fmt::Display, path::PathBuf, str::FromStr};
+use thiserror::Error;
+
+/// Placeholder icon image
+static PLACEHOLDER_ICON: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="144" height="144" viewBox="0 0 144 144">
+  <rect width="144" height="144" rx="24" fill="#4a4a4a" />
+</svg>
+"##;
+
+/// Errors that can occur when writing a scaffold
+#[derive(Debug, Error)]
+pub enum ScaffoldError {
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// File already exists in the target directory
+    #[error("file {} already exists", .0.display())]
+    AlreadyExists(PathBuf),
+}
+
+/// Native binary target (i.e windows-x64)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NativeTarget {
+    /// Target operating system
+    pub os: OperatingSystem,
+    /// Target architecture
+    pub arch: Arch,
+}
+
+impl FromStr for NativeTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (os, arch) = s
+            .split_once('-')
+            .ok_or_else(|| format!("invalid target \"{s}\", expected os-arch (i.e windows-x64)"))?;
+
+        Ok(NativeTarget {
+            os: os
+                .parse()
+                .map_err(|_| format!("unknown operating system \"{os}\""))?,
+            arch: arch
+                .parse()
+                .map_err(|_| format!("unknown architecture \"{arch}\""))?,
+        })
+    }
+}
+
+impl Display for NativeTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.os, self.arch)
+    }
+}
+
+/// Binary to scaffold a plugin with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScaffoldBinary {
+    /// Node script entrypoint
+    Node,
+    /// Native binaries for each of the targets
+    Native(Vec<NativeTarget>),
+}
+
+/// Options for scaffolding a new plugin
+#[derive(Debug, Clone)]
+pub struct PluginScaffold {
+    /// ID of the plugin
+    pub id: PluginId,
+    /// Name of the plugin
+    pub name: String,
+    /// Authors of the plugin
+    pub authors: Vec<String>,
+    /// Description of the plugin
+    pub description: Option<String>,
+    /// Binary the plugin uses
+    pub binary: ScaffoldBinary,
+}
+
+/// Options for scaffolding a new icon pack
+#[derive(Debug, Clone)]
+pub struct IconPackScaffold {
+    /// ID of the icon pack
+    pub id: IconPackId,
+    /// Name of the icon pack
+    pub name: String,
+    /// Authors of the icon pack
+    pub authors: Vec<String>,
+    /// Description of the icon pack
+    pub description: Option<String>,
+}
+
+/// File within a scaffold
+#[derive(Debug, Clone)]
+pub struct ScaffoldFile {
+    /// Path to the file relative to the project root
+    pub path: ManifestPath,
+    /// Contents of the file
+    pub contents: String,
+    /// Whether the file is marked as executable, only applies on unix
+    pub executable: bool,
+}
+
+/// Generated manifest along with the files it references
+#[derive(Debug, Clone)]
+pub struct Scaffold<M> {
+    /// Generated manifest
+    pub manifest: M,
+    /// Files to create alongside the manifest
+    pub files: Vec<ScaffoldFile>,
+}
+
+impl PluginScaffold {
+    /// Generate the plugin manifest and files, the manifest is
+    /// validated using the default [ValidationOptions]
+    pub fn build(&self) -> Result<Scaffold<PluginManifest>, ManifestError> {
+        let icon = ManifestPath("images/icon.svg".to_string());
+        let inspector = ManifestPath("inspector/index.html".to_string());
+
+        let mut files = vec![
+            ScaffoldFile {
+                path: icon.clone(),
+                contents: PLACEHOLDER_ICON.to_string(),
+                executable: false,
+            },
+            ScaffoldFile {
+                path: inspector.clone(),
+                contents: inspector_html(&self.name),
+                executable: false,
+            },
+        ];
+
+        let bin = match &self.binary {
+            ScaffoldBinary::Node => {
+                let entrypoint = ManifestPath("bin/index.js".to_string());
+                files.push(ScaffoldFile {
+                    path: entrypoint.clone(),
+                    contents: format!("// Entrypoint for the {} plugin\n", self.name),
+                    executable: false,
+                });
+
+                MBin::Node {
+                    node: MBinNode {
+                        entrypoint,
+                        version: default_node_version(),
+                    },
+                }
+            }
+            // Native binaries are built by the author, placeholders are created
+            // in their place so the bundle is valid until they are replaced
+            ScaffoldBinary::Native(targets) => MBin::Native {
+                native: targets
+                    .iter()
+                    .map(|target| {
+                        let path = native_path(target);
+                        files.push(ScaffoldFile {
+                            path: path.clone(),
+                            contents: placeholder_binary(&self.name, target),
+                            executable: target.os != OperatingSystem::Windows,
+                        });
+
+                        MBinNative {
+                            os: target.os,
+                            arch: target.arch,
+                            path,
+                        }
+                    })
+                    .collect(),
+            },
+        };
+
+        let mut actions = IndexMap::new();
+        actions.insert(
+            ActionId("example".to_string()),
+            ManifestAction {
+                label: "Example".to_string(),
+                icon: Some(icon.clone()),
+                display: None,
+                icon_options: None,
+                description: Some("Example action".to_string()),
+                inspector: Some(inspector),
+                settings: None,
+            },
+        );
+
+        let manifest = PluginManifest {
+            manifest_version: CURRENT_MANIFEST_VERSION,
+            plugin: MPlugin {
+                id: self.id.clone(),
+                name: self.name.clone(),
+                version: ManifestVersion::new(0, 1, 0),
+                authors: self.authors.clone(),
+                description: self.description.clone(),
+                icon: Some(icon.clone()),
+                internal: None,
+            },
+            engines: None,
+            bin: Some(bin),
+            category: MCategory {
+                label: self.name.clone(),
+                icon: Some(icon),
+            },
+            actions: ActionMap(actions),
+            settings: None,
+            permissions: None,
+        };
+
+        manifest.validate_with(&ValidationOptions::default())?;
+        Ok(Scaffold { manifest, files })
+    }
+}
+
+impl IconPackScaffold {
+    /// Generate the icon pack manifest and files, the manifest is
+    /// validated using the default [ValidationOptions]
+    pub fn build(&self) -> Result<Scaffold<IconsManifest>, ManifestError> {
+        let icon = ManifestPath("images/icon.svg".to_string());
+        let example = ManifestPath("icons/example.svg".to_string());
+
+        let files = vec![
+            ScaffoldFile {
+                path: icon.clone(),
+                contents: PLACEHOLDER_ICON.to_string(),
+                executable: false,
+            },
+            ScaffoldFile {
+                path: example.clone(),
+                contents: PLACEHOLDER_ICON.to_string(),
+                executable: false,
+            },
+        ];
+
+        let manifest = IconsManifest {
+            manifest_version: CURRENT_MANIFEST_VERSION,
+            icons: MIconPack {
+                id: self.id.clone(),
+                name: self.name.clone(),
+                version: ManifestVersion::new(0, 1, 0),
+                authors: self.authors.clone(),
+                description: self.description.clone(),
+                icon: Some(icon),
+            },
+            engines: None,
+            list: IconList(vec![Icon {
+                path: example,
+                name: "Example".to_string(),
+                tags: Vec::new(),
+            }]),
+        };
+
+        manifest.validate_with(&ValidationOptions::default())?;
+        Ok(Scaffold { manifest, files })
+    }
+}
+
+impl<M> Scaffold<M>
+where
+    M: Serialize,
+{
+    /// Write the manifest and files into `root_dir` using the provided
+    /// manifest `format`, existing files are never overwritten
+    pub fn write(
+        &self,
+        root_dir: &std::path::Path,
+        format: ManifestFormat,
+    ) -> Result<Vec<PathBuf>, ScaffoldError> {
+        let extension = format.extensions().first().copied().unwrap_or("json");
+        let manifest_path = root_dir.join(format!("manifest.{extension}"));
+        let manifest = format.serialize(&self.manifest)?;
+
+        let mut outputs = vec![(manifest_path, manifest, false)];
+        for file in &self.files {
+            let path = file
+                .path
+                .resolve(root_dir)
+                .map_err(ManifestError::Validation)?;
+            outputs.push((path, file.contents.clone(), file.executable));
+        }
+
+        // Check everything up front to avoid a partially written scaffold
+        if let Some((path, _, _)) = outputs.iter().find(|(path, _, _)| path.exists()) {
+            return Err(ScaffoldError::AlreadyExists(path.clone()));
+        }
+
+        for (path, contents, executable) in &outputs {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, contents)?;
+            if *executable {
+                set_executable(path)?;
+            }
+        }
+
+        Ok(outputs.into_iter().map(|(path, _, _)| path).collect())
+    }
+}
+
+/// Mark the file at `path` as executable
+#[cfg(unix)]
+fn set_executable(path: &std::path::Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+}
+
+/// Files do not need to be marked as executable outside of unix
+#[cfg(not(unix))]
+fn set_executable(_path: &std::path::Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Path for the native binary of a target
+fn native_path(target: &NativeTarget) -> ManifestPath {
+    let extension = match target.os {
+        OperatingSystem::Windows => ".exe",
+        _ => "",
+    };
+
+    ManifestPath(format!("bin/plugin-{target}{extension}"))
+}
+
+/// Placeholder for a native binary, fails when run until replaced
+/// with the built binary
+fn placeholder_binary(name: &str, target: &NativeTarget) -> String {
+    format!(
+        "#!/bin/sh\n\
+        # Placeholder for the {name} plugin {target} binary, replace with the built binary\n\
+        exit 1\n"
+    )
+}
+
+/// Placeholder inspector page
+fn inspector_html(name: &str) -> String {
+    let name = name
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="UTF-8" />
+    <title>{name}</title>
+  </head>
+  <body>
+    <h1>{name}</h1>
+  </body>
+</html>
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle::validate_bundle;
+
+    #[test]
+    fn test_scaffold_node_plugin() {
+        let scaffold = PluginScaffold {
+            id: "com.example.test".parse().unwrap(),
+            name: "Test".to_string(),
+            authors: vec!["Example".to_string()],
+            description: Some("Example plugin".to_string()),
+            binary: ScaffoldBinary::Node,
+        }
+        .build()
+        .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        scaffold.write(dir.path(), ManifestFormat::Json).unwrap();
+
+        let source = std::fs::read_to_string(dir.path().join("manifest.json")).unwrap();
+        let manifest = PluginManifest::parse(&source).unwrap();
+        assert!(manifest.lint().is_empty());
+        assert!(validate_bundle(&manifest, dir.path()).is_ok());
+
+        // Existing files are not overwritten
+        assert!(matches!(
+            scaffold.write(dir.path(), ManifestFormat::Json),
+            Err(ScaffoldError::AlreadyExists(_))
+        ));
+    }
+
+    #[test]
+    fn test_scaffold_native_plugin() {
+        let scaffold = PluginScaffold {
+            id: "com.example.test".parse().unwrap(),
+            name: "Test".to_string(),
+            authors: Vec::new(),
+            description: None,
+            binary: ScaffoldBinary::Native(vec![
+                "windows-x64".parse().unwrap(),
+                "linux-arm64".parse().unwrap(),
+            ]),
+        }
+        .build()
+        .unwrap();
+
+        let Some(MBin::Native { native }) = &scaffold.manifest.bin else {
+            panic!("expected native binary");
+        };
+        let paths: Vec<&str> = native.iter().map(|bin| bin.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["bin/plugin-windows-x64.exe", "bin/plugin-linux-arm64"]
+        );
+
+        // Placeholder binaries keep the fresh bundle valid
+        let dir = tempfile::tempdir().unwrap();
+        scaffold.write(dir.path(), ManifestFormat::Json).unwrap();
+
+        let source = std::fs::read_to_string(dir.path().join("manifest.json")).unwrap();
+        let manifest = PluginManifest::parse(&source).unwrap();
+        assert!(validate_bundle(&manifest, dir.path()).is_ok());
+    }
+
+    #[test]
+    fn test_scaffold_icon_pack() {
+        let scaffold = IconPackScaffold {
+            id: "com.example.pack".parse().unwrap(),
+            name: "Pack".to_string(),
+            authors: Vec::new(),
+            description: None,
+        }
+        .build()
+        .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let written = scaffold.write(dir.path(), ManifestFormat::Toml).unwrap();
+        assert_eq!(written[0], dir.path().join("manifest.toml"));
+
+        let source = std::fs::read_to_string(&written[0]).unwrap();
+        let manifest =
+            IconsManifest::parse_as(&source, ManifestFormat::Toml, &Default::default()).unwrap();
+        assert!(validate_bundle(&manifest, dir.path()).is_ok());
+    }
+
+    #[test]
+    fn test_scaffold_invalid_name_rejected() {
+        let result = PluginScaffold {
+            id: "com.example.test".parse().unwrap(),
+            name: String::new(),
+            authors: Vec::new(),
+            description: None,
+            binary: ScaffoldBinary::Node,
+        }
+        .build();
+        assert!(matches!(result, Err(ManifestError::Validation(_))));
+    }
+
+    #[test]
+    fn test_parse_native_target() {
+        let target: NativeTarget = "macos-arm64".parse().unwrap();
+        assert_eq!(target.os, OperatingSystem::MacOs);
+        assert_eq!(target.arch, Arch::Arm64);
+        assert!("macos".parse::<NativeTarget>().is_err());
+        assert!("beos-x64".parse::<NativeTarget>().is_err());
+    }
+}
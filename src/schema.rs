@@ -0,0 +1,90 @@
+//! # Schema
+//!
+//! JSON schemas for the manifests, allows hosts to serve the schemas
+//! to editors and registries at runtime
+
+use crate::{icons::IconsManifest, plugin::PluginManifest};
+use schemars::{JsonSchema, Schema, generate::SchemaSettings};
+use strum::{Display, EnumString};
+
+/// JSON schema draft to generate schemas for
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+pub enum SchemaDraft {
+    /// Draft 07, used for the published schemas
+    #[default]
+    #[strum(serialize = "draft-07")]
+    Draft07,
+    /// Draft 2020-12
+    #[strum(serialize = "2020-12")]
+    Draft2020_12,
+}
+
+impl SchemaDraft {
+    /// Settings for generating schemas using the draft
+    fn settings(&self) -> SchemaSettings {
+        match self {
+            SchemaDraft::Draft07 => SchemaSettings::draft07(),
+            SchemaDraft::Draft2020_12 => SchemaSettings::draft2020_12(),
+        }
+    }
+}
+
+/// Generate the schema for a plugin manifest using [SchemaDraft::Draft07]
+pub fn plugin_schema() -> Schema {
+    plugin_schema_with(SchemaDraft::default())
+}
+
+/// Generate the schema for a plugin manifest using the provided `draft`
+pub fn plugin_schema_with(draft: SchemaDraft) -> Schema {
+    schema_for::<PluginManifest>(draft)
+}
+
+/// Generate the schema for an icon pack manifest using [SchemaDraft::Draft07]
+pub fn icons_schema() -> Schema {
+    icons_schema_with(SchemaDraft::default())
+}
+
+/// Generate the schema for an icon pack manifest using the provided `draft`
+pub fn icons_schema_with(draft: SchemaDraft) -> Schema {
+    schema_for::<IconsManifest>(draft)
+}
+
+/// Generate the schema for `T` using the provided `draft`
+pub fn schema_for<T>(draft: SchemaDraft) -> Schema
+where
+    T: JsonSchema,
+{
+    draft
+        .settings()
+        .into_generator()
+        .into_root_schema_for::<T>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_drafts() {
+        let schema = plugin_schema();
+        assert_eq!(
+            schema.get("$schema").and_then(|value| value.as_str()),
+            Some("http://json-schema.org/draft-07/schema#")
+        );
+        assert!(schema.get("definitions").is_some());
+
+        let schema = icons_schema_with(SchemaDraft::Draft2020_12);
+        assert_eq!(
+            schema.get("$schema").and_then(|value| value.as_str()),
+            Some("https://json-schema.org/draft/2020-12/schema")
+        );
+        assert!(schema.get("$defs").is_some());
+    }
+
+    #[test]
+    fn test_parse_draft() {
+        assert_eq!("draft-07".parse(), Ok(SchemaDraft::Draft07));
+        assert_eq!("2020-12".parse(), Ok(SchemaDraft::Draft2020_12));
+        assert!("draft-04".parse::<SchemaDraft>().is_err());
+    }
+}
@@ -0,0 +1,654 @@
+//! # Settings
+//!
+//! Declarations for the settings stored by actions and plugins, allows
+//! hosts to validate and fill in defaults for saved tile properties and
+//! plugin configuration without relying on the inspector
+
+use crate::{
+    color::Color,
+    validation::{ValidationOptions, validate_name},
+};
+use garde::Validate;
+use indexmap::IndexMap;
+use schemars::JsonSchema;
+use serde::{
+    Deserialize, Deserializer, Serialize,
+    de::{self, MapAccess, Visitor},
+};
+use serde_json::{Map, Number, Value};
+use serde_with::skip_serializing_none;
+use std::{fmt::Display, str::FromStr};
+
+/// Key for a setting within a [SettingsSchema]
+///
+/// Keys follow the [a-zA-Z_-] format (i.e api_key, refresh-rate, Mode)
+#[derive(
+    Debug, Clone, Serialize, Deserialize, Validate, Hash, PartialEq, Eq, PartialOrd, Ord, JsonSchema,
+)]
+#[garde(transparent)]
+#[serde(transparent)]
+pub struct SettingKey(#[garde(custom(validate_name))] pub String);
+
+impl SettingKey {
+    /// Get the inner key as a [str] slice
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl TryFrom<String> for SettingKey {
+    type Error = garde::Report;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let value = SettingKey(value);
+        value.validate()?;
+        Ok(value)
+    }
+}
+
+impl FromStr for SettingKey {
+    type Err = garde::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s.to_string())
+    }
+}
+
+impl AsRef<str> for SettingKey {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for SettingKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Ordered map of settings stored by an action or plugin
+///
+/// Duplicate keys are rejected when deserializing rather than
+/// overwriting the previous setting
+#[derive(Debug, Default, Clone, Serialize, JsonSchema)]
+pub struct SettingsSchema(pub IndexMap<SettingKey, SettingField>);
+
+impl<'de> Deserialize<'de> for SettingsSchema {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct SettingsSchemaVisitor;
+
+        impl<'de> Visitor<'de> for SettingsSchemaVisitor {
+            type Value = SettingsSchema;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a map of settings")
+            }
+
+            fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut settings = IndexMap::with_capacity(access.size_hint().unwrap_or_default());
+
+                while let Some(key) = access.next_key::<SettingKey>()? {
+                    if settings.contains_key(&key) {
+                        return Err(de::Error::custom(format!(
+                            "duplicate setting key \"{key}\""
+                        )));
+                    }
+
+                    let field: SettingField = access.next_value()?;
+                    settings.insert(key, field);
+                }
+
+                Ok(SettingsSchema(settings))
+            }
+        }
+
+        deserializer.deserialize_map(SettingsSchemaVisitor)
+    }
+}
+
+impl AsRef<IndexMap<SettingKey, SettingField>> for SettingsSchema {
+    fn as_ref(&self) -> &IndexMap<SettingKey, SettingField> {
+        &self.0
+    }
+}
+
+impl SettingsSchema {
+    /// Get the default values for all the settings that declare
+    /// a default value
+    pub fn defaults(&self) -> Map<String, Value> {
+        self.0
+            .iter()
+            .filter_map(|(key, field)| Some((key.to_string(), field.default_value()?)))
+            .collect()
+    }
+
+    /// Fill in the default values for the settings missing from `settings`,
+    /// settings that are already present (including null) are kept
+    pub fn apply_defaults(&self, settings: &mut Map<String, Value>) {
+        for (key, field) in &self.0 {
+            if settings.contains_key(key.as_str()) {
+                continue;
+            }
+
+            if let Some(default) = field.default_value() {
+                settings.insert(key.to_string(), default);
+            }
+        }
+    }
+
+    /// Get the keys of the settings that must be stored securely by the host
+    pub fn secrets(&self) -> impl Iterator<Item = &SettingKey> {
+        self.0
+            .iter()
+            .filter(|(_, field)| field.is_secret())
+            .map(|(key, _)| key)
+    }
+
+    /// Validate the stored `settings` object against the schema, settings
+    /// that are missing (or null) are only rejected when they are required
+    ///
+    /// Settings that are not declared in the schema are ignored
+    pub fn validate_settings(
+        &self,
+        settings: &Value,
+        options: &ValidationOptions,
+    ) -> Result<(), garde::Report> {
+        let mut report = garde::Report::new();
+
+        let Some(settings) = settings.as_object() else {
+            report.append(
+                garde::Path::empty(),
+                garde::Error::new("expected an object"),
+            );
+            return Err(report);
+        };
+
+        for (key, field) in &self.0 {
+            let path = garde::Path::new(key.as_str());
+
+            match settings.get(key.as_str()) {
+                None | Some(Value::Null) => {
+                    if field.is_required() {
+                        report.append(path, garde::Error::new("setting is required"));
+                    }
+                }
+                Some(value) => {
+                    if let Err(error) = field.validate_value(value, options) {
+                        report.append(path, error);
+                    }
+                }
+            }
+        }
+
+        if report.is_empty() {
+            Ok(())
+        } else {
+            Err(report)
+        }
+    }
+}
+
+/// Definition of a single setting
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct SettingField {
+    /// Label for the setting
+    #[schemars(example = "Refresh rate")]
+    pub label: Option<String>,
+
+    /// Description of what the setting is used for
+    pub description: Option<String>,
+
+    /// Placeholder shown in the inspector form when the setting is empty
+    pub placeholder: Option<String>,
+
+    /// Whether the setting must always have a value
+    pub required: Option<bool>,
+
+    /// Whether the value is sensitive (i.e API keys) and must be stored
+    /// securely by the host (i.e within the system keychain)
+    ///
+    /// Only string settings within the plugin settings can be secret
+    pub secret: Option<bool>,
+
+    /// Type of the setting along with its constraints
+    #[serde(flatten)]
+    pub kind: SettingKind,
+}
+
+impl SettingField {
+    /// Whether the setting must always have a value
+    pub fn is_required(&self) -> bool {
+        self.required.unwrap_or_default()
+    }
+
+    /// Whether the setting must be stored securely by the host
+    pub fn is_secret(&self) -> bool {
+        self.secret.unwrap_or_default()
+    }
+
+    /// Get the default value for the setting as JSON
+    pub fn default_value(&self) -> Option<Value> {
+        match &self.kind {
+            SettingKind::String { default, .. } => default.clone().map(Value::String),
+            SettingKind::Number { default, .. } => default.clone().map(Value::Number),
+            SettingKind::Bool { default } => default.map(Value::Bool),
+            SettingKind::Enum { default, .. } => default.clone().map(Value::String),
+            SettingKind::Color { default } => default.map(|color| Value::String(color.to_string())),
+            SettingKind::File { .. } => None,
+        }
+    }
+
+    /// Validate a stored `value` for the setting
+    pub fn validate_value(&self, value: &Value, options: &ValidationOptions) -> garde::Result {
+        match &self.kind {
+            SettingKind::String {
+                min_length,
+                max_length,
+                ..
+            } => {
+                let length = expect_str(value)?.chars().count();
+
+                if let Some(min_length) = min_length
+                    && length < *min_length
+                {
+                    return Err(garde::Error::new(format!(
+                        "length must not be less than {min_length}"
+                    )));
+                }
+
+                if let Some(max_length) = max_length
+                    && length > *max_length
+                {
+                    return Err(garde::Error::new(format!(
+                        "length must not be greater than {max_length}"
+                    )));
+                }
+            }
+            SettingKind::Number { min, max, .. } => {
+                let value = value
+                    .as_f64()
+                    .ok_or_else(|| garde::Error::new("expected a number"))?;
+
+                if let Some(min) = min
+                    && value < *min
+                {
+                    return Err(garde::Error::new(format!(
+                        "value must not be less than {min}"
+                    )));
+                }
+
+                if let Some(max) = max
+                    && value > *max
+                {
+                    return Err(garde::Error::new(format!(
+                        "value must not be greater than {max}"
+                    )));
+                }
+            }
+            SettingKind::Bool { .. } => {
+                if !value.is_boolean() {
+                    return Err(garde::Error::new("expected a boolean"));
+                }
+            }
+            SettingKind::Enum {
+                options: choices, ..
+            } => {
+                let value = expect_str(value)?;
+                if !choices.iter().any(|choice| choice.value == value) {
+                    let choices = choices
+                        .iter()
+                        .map(|choice| format!("\"{}\"", choice.value))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    return Err(garde::Error::new(format!("value must be one of {choices}")));
+                }
+            }
+            SettingKind::Color { .. } => {
+                Color::parse(expect_str(value)?, options.color_mode)?;
+            }
+            SettingKind::File { extensions } => {
+                let value = expect_str(value)?;
+                if !extensions.is_empty() {
+                    let extension = file_extension(value).unwrap_or_default();
+                    if !extensions
+                        .iter()
+                        .any(|allowed| allowed.eq_ignore_ascii_case(extension))
+                    {
+                        return Err(garde::Error::new(format!(
+                            "file must have one of the extensions: {}",
+                            extensions.join(", ")
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Type of a setting along with its default value and constraints
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SettingKind {
+    /// Text value
+    String {
+        /// Default value for the setting
+        default: Option<String>,
+        /// Minimum number of characters
+        min_length: Option<usize>,
+        /// Maximum number of characters
+        max_length: Option<usize>,
+    },
+
+    /// Numeric value
+    Number {
+        /// Default value for the setting
+        default: Option<Number>,
+        /// Minimum allowed value (inclusive)
+        min: Option<f64>,
+        /// Maximum allowed value (inclusive)
+        max: Option<f64>,
+        /// Amount the value changes by when stepped in the
+        /// inspector form
+        step: Option<f64>,
+    },
+
+    /// True or false value
+    Bool {
+        /// Default value for the setting
+        default: Option<bool>,
+    },
+
+    /// Value from a fixed set of options
+    Enum {
+        /// Options to choose from
+        options: Vec<SettingOption>,
+        /// Default value for the setting, must be the value
+        /// of one of the options
+        default: Option<String>,
+    },
+
+    /// Color value
+    Color {
+        /// Default value for the setting
+        default: Option<Color>,
+    },
+
+    /// Path to a file chosen by the user
+    File {
+        /// File extensions that are accepted (i.e png, jpg), any
+        /// file is accepted when empty
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        extensions: Vec<String>,
+    },
+}
+
+/// Option for an enum setting
+#[skip_serializing_none]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct SettingOption {
+    /// Value stored when the option is chosen
+    #[schemars(example = &"fast")]
+    pub value: String,
+
+    /// Label for the option, the value is shown when missing
+    #[schemars(example = &"Fast")]
+    pub label: Option<String>,
+}
+
+/// Get the value as a string
+fn expect_str(value: &Value) -> Result<&str, garde::Error> {
+    value
+        .as_str()
+        .ok_or_else(|| garde::Error::new("expected a string"))
+}
+
+/// Get the extension of the file name at the end of a host path
+fn file_extension(path: &str) -> Option<&str> {
+    let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    file_name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension)
+        .filter(|extension| !extension.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema(source: Value) -> SettingsSchema {
+        serde_json::from_value(source).unwrap()
+    }
+
+    fn errors(schema: &SettingsSchema) -> Vec<String> {
+        match schema.validate_with(&ValidationOptions::default()) {
+            Ok(()) => Vec::new(),
+            Err(report) => report
+                .iter()
+                .map(|(path, error)| format!("{path}: {error}"))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_settings_schema() {
+        let schema = schema(json!({
+            "name": { "type": "string", "label": "Name", "max_length": 16, "default": "Tile" },
+            "speed": { "type": "number", "min": 0, "max": 10, "default": 5 },
+            "enabled": { "type": "bool", "default": true },
+            "mode": {
+                "type": "enum",
+                "options": [{ "value": "fast", "label": "Fast" }, { "value": "slow" }],
+                "default": "slow"
+            },
+            "color": { "type": "color", "default": "#ff0000" },
+            "image": { "type": "file", "extensions": ["png", "jpg"], "required": true }
+        }));
+
+        let keys: Vec<&str> = schema.0.keys().map(SettingKey::as_str).collect();
+        assert_eq!(keys, ["name", "speed", "enabled", "mode", "color", "image"]);
+        assert!(matches!(
+            schema.0[2].kind,
+            SettingKind::Bool {
+                default: Some(true)
+            }
+        ));
+        assert!(schema.0[5].is_required());
+        assert!(errors(&schema).is_empty());
+
+        assert_eq!(
+            Value::Object(schema.defaults()),
+            json!({
+                "name": "Tile",
+                "speed": 5,
+                "enabled": true,
+                "mode": "slow",
+                "color": "#ff0000"
+            })
+        );
+    }
+
+    #[test]
+    fn test_apply_defaults() {
+        let schema = schema(json!({
+            "name": { "type": "string", "default": "Tile" },
+            "speed": { "type": "number", "default": 5 },
+            "ratio": { "type": "number", "default": 2.5 },
+            "enabled": { "type": "bool", "default": true },
+            "image": { "type": "file" }
+        }));
+
+        let mut settings = json!({ "name": "Custom", "enabled": null })
+            .as_object()
+            .cloned()
+            .unwrap();
+        schema.apply_defaults(&mut settings);
+
+        assert_eq!(
+            Value::Object(settings),
+            json!({
+                "name": "Custom",
+                "enabled": null,
+                "speed": 5,
+                "ratio": 2.5
+            })
+        );
+    }
+
+    #[test]
+    fn test_non_finite_number_constraints_rejected() {
+        let schema: SettingsSchema = toml::from_str(
+            r#"
+            speed = { type = "number", min = nan, max = inf, step = -inf }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            errors(&schema),
+            [
+                "speed.min: min must be a finite number",
+                "speed.max: max must be a finite number",
+                "speed.step: step must be a finite number",
+                "speed.step: step must be greater than 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_settings_schema_rejects_duplicate_keys() {
+        let source = r#"{
+            "mode": { "type": "bool" },
+            "mode": { "type": "string" }
+        }"#;
+
+        let error = serde_json::from_str::<SettingsSchema>(source).unwrap_err();
+        assert!(error.to_string().contains("duplicate setting key \"mode\""));
+    }
+
+    #[test]
+    fn test_settings_schema_validation() {
+        let schema = schema(json!({
+            "1st": { "type": "bool" },
+            "name": { "type": "string", "label": "", "min_length": 4, "max_length": 2 },
+            "speed": { "type": "number", "min": 0, "max": 10, "default": 20 },
+            "mode": {
+                "type": "enum",
+                "options": [{ "value": "a" }, { "value": "a" }],
+                "default": "b"
+            },
+            "empty": { "type": "enum", "options": [] },
+            "color": { "type": "color", "default": "white" },
+            "image": { "type": "file", "extensions": [".png"] }
+        }));
+
+        let errors = errors(&schema);
+        let paths: Vec<&str> = errors
+            .iter()
+            .map(|error| error.split_once(':').unwrap().0)
+            .collect();
+
+        assert_eq!(
+            paths,
+            [
+                "1st",
+                "name.label",
+                "name.max_length",
+                "speed.default",
+                "mode.options[1].value",
+                "mode.default",
+                "empty.options",
+                "color.default",
+                "image.extensions[0]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_secret_settings_validation() {
+        let schema = schema(json!({
+            "api_key": { "type": "string", "secret": true, "required": true },
+            "region": { "type": "enum", "options": [{ "value": "eu" }], "secret": true },
+            "token": { "type": "string", "secret": true, "default": "abc" }
+        }));
+
+        let keys: Vec<&str> = schema.secrets().map(SettingKey::as_str).collect();
+        assert_eq!(keys, ["api_key", "region", "token"]);
+
+        let errors = errors(&schema);
+        assert_eq!(
+            errors,
+            [
+                "region.secret: only string settings can be secret",
+                "token.default: secret settings must not have a default value",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_settings() {
+        let schema = schema(json!({
+            "name": { "type": "string", "max_length": 4 },
+            "speed": { "type": "number", "min": 0, "max": 10 },
+            "enabled": { "type": "bool" },
+            "mode": { "type": "enum", "options": [{ "value": "fast" }, { "value": "slow" }] },
+            "color": { "type": "color" },
+            "image": { "type": "file", "extensions": ["png"], "required": true }
+        }));
+        let options = ValidationOptions::default();
+
+        assert!(
+            schema
+                .validate_settings(
+                    &json!({
+                        "name": "Tile",
+                        "speed": 2.5,
+                        "enabled": false,
+                        "mode": "fast",
+                        "color": "rgb(255 0 0)",
+                        "image": "C:\\Users\\Example\\image.PNG",
+                        "other": "ignored"
+                    }),
+                    &options
+                )
+                .is_ok()
+        );
+
+        let report = schema
+            .validate_settings(
+                &json!({
+                    "name": "Too long",
+                    "speed": 11,
+                    "enabled": "yes",
+                    "mode": "medium",
+                    "color": "not a color",
+                    "image": null
+                }),
+                &options,
+            )
+            .unwrap_err();
+        let errors: Vec<String> = report
+            .iter()
+            .map(|(path, error)| format!("{path}: {error}"))
+            .collect();
+
+        assert_eq!(errors[0], "name: length must not be greater than 4");
+        assert_eq!(errors[1], "speed: value must not be greater than 10");
+        assert_eq!(errors[2], "enabled: expected a boolean");
+        assert_eq!(errors[3], "mode: value must be one of \"fast\", \"slow\"");
+        assert!(errors[4].starts_with("color: "));
+        assert_eq!(errors[5], "image: setting is required");
+
+        assert!(schema.validate_settings(&json!([]), &options).is_err());
+    }
+}
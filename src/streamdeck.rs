@@ -0,0 +1,648 @@
+//! # Stream Deck
+//!
+//! Importer for Elgato Stream Deck plugin manifests, converts the
+//! Stream Deck `manifest.json` into a [PluginManifest] along with a
+//! report of anything that could not be mapped
+
+use crate::{
+    ManifestError,
+    migration::CURRENT_MANIFEST_VERSION,
+    path::ManifestPath,
+    plugin::{
+        ActionId, ActionMap, BinaryNodeVersion, MBin, MBinNative, MBinNode, MCategory, MPlugin,
+        ManifestAction, PluginId, PluginManifest, default_node_version,
+    },
+    system::{Arch, OperatingSystem},
+    validation::ValidationOptions,
+    version::ManifestVersion,
+};
+use garde::Validate;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Extensions of Stream Deck code paths that run using node
+static NODE_EXTENSIONS: [&str; 3] = ["js", "mjs", "cjs"];
+
+/// Stream Deck plugin manifest, only the fields that can
+/// be mapped are declared, everything else is collected
+/// into `other` to be reported
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct StreamDeckManifest {
+    #[serde(rename = "UUID")]
+    uuid: String,
+    name: String,
+    version: String,
+    author: Option<String>,
+    description: Option<String>,
+    icon: Option<String>,
+    category: Option<String>,
+    category_icon: Option<String>,
+    code_path: Option<String>,
+    code_path_mac: Option<String>,
+    code_path_win: Option<String>,
+    property_inspector_path: Option<String>,
+    nodejs: Option<StreamDeckNode>,
+    #[serde(rename = "OS", default)]
+    os: Vec<StreamDeckOs>,
+    #[serde(default)]
+    actions: Vec<StreamDeckAction>,
+    #[serde(flatten)]
+    other: IndexMap<String, Value>,
+}
+
+/// Node configuration for a Stream Deck plugin
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct StreamDeckNode {
+    version: Option<String>,
+    #[serde(flatten)]
+    other: IndexMap<String, Value>,
+}
+
+/// Operating system supported by a Stream Deck plugin
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct StreamDeckOs {
+    platform: String,
+}
+
+/// Action within a Stream Deck plugin
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct StreamDeckAction {
+    #[serde(rename = "UUID")]
+    uuid: String,
+    name: String,
+    icon: Option<String>,
+    tooltip: Option<String>,
+    property_inspector_path: Option<String>,
+    #[serde(default)]
+    states: Vec<StreamDeckState>,
+    #[serde(flatten)]
+    other: IndexMap<String, Value>,
+}
+
+/// State of a Stream Deck action
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct StreamDeckState {
+    image: Option<String>,
+    #[serde(flatten)]
+    other: IndexMap<String, Value>,
+}
+
+/// Note about a field within the Stream Deck manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportNote {
+    /// Path to the field within the Stream Deck manifest (i.e Actions[0].States)
+    pub path: String,
+    /// Message describing the note
+    pub message: String,
+}
+
+/// Report of the fields that were changed or could not be mapped
+/// when importing a Stream Deck manifest
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportReport {
+    /// Fields that were mapped but required changes (i.e adding a
+    /// missing file extension)
+    pub adjusted: Vec<ImportNote>,
+    /// Fields that could not be mapped and were dropped
+    pub unmapped: Vec<ImportNote>,
+}
+
+impl ImportReport {
+    fn adjust(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.adjusted.push(ImportNote {
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+
+    fn unmap(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.unmapped.push(ImportNote {
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+
+    /// Report all the `other` fields under `parent` as unmapped
+    fn unmap_other(&mut self, parent: &str, other: &IndexMap<String, Value>) {
+        for key in other.keys() {
+            let path = if parent.is_empty() {
+                key.clone()
+            } else {
+                format!("{parent}.{key}")
+            };
+
+            self.unmap(path, "field has no tilepad equivalent");
+        }
+    }
+}
+
+/// Import a Stream Deck plugin `manifest.json` as a [PluginManifest]
+///
+/// The imported manifest is validated using the default [ValidationOptions]
+pub fn import_stream_deck(source: &str) -> Result<(PluginManifest, ImportReport), ManifestError> {
+    let manifest: StreamDeckManifest = serde_json::from_str(source)?;
+    let mut report = ImportReport::default();
+
+    let plugin = MPlugin {
+        id: PluginId(manifest.uuid.clone()),
+        name: manifest.name.clone(),
+        version: import_version(&manifest.version, &mut report),
+        authors: manifest.author.clone().into_iter().collect(),
+        description: manifest.description.clone(),
+        icon: manifest
+            .icon
+            .as_deref()
+            .map(|icon| import_image(icon, "Icon", &mut report)),
+        internal: None,
+    };
+
+    let category = MCategory {
+        label: match &manifest.category {
+            Some(category) => category.clone(),
+            None => {
+                report.adjust("Category", "category not specified, using the plugin name");
+                manifest.name.clone()
+            }
+        },
+        icon: manifest
+            .category_icon
+            .as_deref()
+            .map(|icon| import_image(icon, "CategoryIcon", &mut report)),
+    };
+
+    let bin = import_bin(&manifest, &mut report);
+    let actions = import_actions(&manifest, &mut report);
+
+    report.unmap_other("", &manifest.other);
+
+    let manifest = PluginManifest {
+        manifest_version: CURRENT_MANIFEST_VERSION,
+        plugin,
+        engines: None,
+        bin,
+        category,
+        actions,
+        settings: None,
+        permissions: None,
+    };
+
+    manifest.validate_with(&ValidationOptions::default())?;
+    Ok((manifest, report))
+}
+
+/// Stream Deck versions use 4 components (i.e 1.0.0.0), the
+/// fourth is dropped to form a semver version
+fn import_version(version: &str, report: &mut ImportReport) -> ManifestVersion {
+    let mut parts: Vec<u64> = Vec::new();
+    for part in version.split('.') {
+        match part.parse() {
+            Ok(value) => parts.push(value),
+            Err(_) => {
+                report.adjust(
+                    "Version",
+                    format!("version part \"{part}\" is not a number, replaced with 0"),
+                );
+                parts.push(0);
+            }
+        }
+    }
+    parts.resize(parts.len().max(3), 0);
+
+    let imported = ManifestVersion::new(parts[0], parts[1], parts[2]);
+    if imported.to_string() != version {
+        report.adjust(
+            "Version",
+            format!("converted version \"{version}\" to \"{imported}\""),
+        );
+    }
+
+    imported
+}
+
+/// Stream Deck image paths omit the file extension, the standard
+/// resolution ".png" image is assumed even when only a ".svg" exists
+fn import_image(path: &str, field: &str, report: &mut ImportReport) -> ManifestPath {
+    let path = ManifestPath(path.replace('\\', "/"));
+    if path.extension().is_some() {
+        return path;
+    }
+
+    report.adjust(
+        field,
+        format!(
+            "image \"{path}\" has no extension, assuming \"{path}.png\" (update the \
+             extension if the image is a \".svg\")"
+        ),
+    );
+    ManifestPath(format!("{path}.png"))
+}
+
+/// Map the Stream Deck code paths to a binary
+fn import_bin(manifest: &StreamDeckManifest, report: &mut ImportReport) -> Option<MBin> {
+    let is_node = |path: &str| {
+        ManifestPath(path.to_string())
+            .extension()
+            .is_some_and(|extension| NODE_EXTENSIONS.contains(&extension))
+    };
+
+    if let Some(code_path) = manifest.code_path.as_deref()
+        && (manifest.nodejs.is_some() || is_node(code_path))
+    {
+        let version = match manifest.nodejs.as_ref() {
+            Some(node) => {
+                report.unmap_other("Nodejs", &node.other);
+                import_node_version(node, report)
+            }
+            None => default_node_version(),
+        };
+
+        let platform_paths = [
+            ("CodePathMac", &manifest.code_path_mac),
+            ("CodePathWin", &manifest.code_path_win),
+        ];
+
+        for (field, path) in platform_paths {
+            if path.is_some() {
+                report.unmap(
+                    field,
+                    "node plugins use a single entrypoint for all platforms",
+                );
+            }
+        }
+
+        return Some(MBin::Node {
+            node: MBinNode {
+                entrypoint: ManifestPath(code_path.replace('\\', "/")),
+                version,
+            },
+        });
+    }
+
+    // HTML plugins run within a browser environment
+    if let Some(code_path) = manifest.code_path.as_deref()
+        && ManifestPath(code_path.to_string())
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("html"))
+    {
+        report.unmap("CodePath", "HTML plugins are not supported");
+        return None;
+    }
+
+    let mut native = Vec::new();
+    let mut push = |os: OperatingSystem, path: &str| {
+        let path = ManifestPath(path.replace('\\', "/"));
+        // macOS binaries are assumed to be universal
+        let archs: &[Arch] = match os {
+            OperatingSystem::MacOs => &[Arch::X64, Arch::Arm64],
+            _ => &[Arch::X64],
+        };
+
+        for arch in archs {
+            native.push(MBinNative {
+                os,
+                arch: *arch,
+                path: path.clone(),
+            });
+        }
+    };
+
+    let platforms = [
+        (OperatingSystem::Windows, "windows", &manifest.code_path_win),
+        (OperatingSystem::MacOs, "mac", &manifest.code_path_mac),
+    ];
+
+    for (os, platform, path) in platforms {
+        let supported = manifest.os.is_empty()
+            || manifest
+                .os
+                .iter()
+                .any(|value| value.platform.eq_ignore_ascii_case(platform));
+
+        match (path, &manifest.code_path) {
+            (Some(path), _) => push(os, path),
+            (None, Some(path)) if supported => push(os, path),
+            _ => {}
+        }
+    }
+
+    if native.is_empty() {
+        report.unmap("CodePath", "plugin does not specify a code path");
+        return None;
+    }
+
+    Some(MBin::Native { native })
+}
+
+/// Map the Stream Deck node version (i.e 20) to a version range
+fn import_node_version(node: &StreamDeckNode, report: &mut ImportReport) -> BinaryNodeVersion {
+    let Some(version) = node.version.as_deref() else {
+        return default_node_version();
+    };
+
+    match node_semver::Range::parse(version) {
+        Ok(range) => BinaryNodeVersion(range),
+        Err(_) => {
+            report.unmap(
+                "Nodejs.Version",
+                format!("node version \"{version}\" is not a valid version range"),
+            );
+            default_node_version()
+        }
+    }
+}
+
+/// Map the Stream Deck actions to tilepad actions
+fn import_actions(manifest: &StreamDeckManifest, report: &mut ImportReport) -> ActionMap {
+    let mut actions = IndexMap::with_capacity(manifest.actions.len());
+
+    for (index, action) in manifest.actions.iter().enumerate() {
+        let field = format!("Actions[{index}]");
+        let action_id = import_action_id(&manifest.uuid, &action.uuid, index, &actions);
+        if action.uuid != format!("{}.{action_id}", manifest.uuid) {
+            report.adjust(
+                format!("{field}.UUID"),
+                format!("action \"{}\" imported as \"{action_id}\"", action.uuid),
+            );
+        }
+
+        // Actions without an icon use the image from their first state
+        let icon = match (&action.icon, action.states.first()) {
+            (Some(icon), _) => Some(import_image(icon, &format!("{field}.Icon"), report)),
+            (
+                None,
+                Some(StreamDeckState {
+                    image: Some(image), ..
+                }),
+            ) => Some(import_image(
+                image,
+                &format!("{field}.States[0].Image"),
+                report,
+            )),
+            _ => None,
+        };
+
+        let inspector = action
+            .property_inspector_path
+            .as_ref()
+            .or(manifest.property_inspector_path.as_ref())
+            .map(|path| ManifestPath(path.replace('\\', "/")));
+
+        for (state_index, state) in action.states.iter().enumerate() {
+            report.unmap_other(&format!("{field}.States[{state_index}]"), &state.other);
+        }
+
+        if action.states.len() > 1 {
+            report.unmap(
+                format!("{field}.States"),
+                "actions with multiple states are not supported",
+            );
+        }
+
+        report.unmap_other(&field, &action.other);
+
+        actions.insert(
+            action_id,
+            ManifestAction {
+                label: action.name.clone(),
+                icon,
+                display: None,
+                icon_options: None,
+                description: action.tooltip.clone(),
+                inspector,
+                settings: None,
+            },
+        );
+    }
+
+    ActionMap(actions)
+}
+
+/// Derive an action ID from a Stream Deck action UUID, the plugin
+/// UUID prefix is removed and invalid characters are replaced
+fn import_action_id(
+    plugin_uuid: &str,
+    action_uuid: &str,
+    index: usize,
+    actions: &IndexMap<ActionId, ManifestAction>,
+) -> ActionId {
+    let name = action_uuid
+        .strip_prefix(plugin_uuid)
+        .and_then(|name| name.strip_prefix('.'))
+        .unwrap_or(action_uuid);
+
+    let name: String = name
+        .chars()
+        .map(|char| {
+            if char.is_ascii_alphanumeric() || char == '-' || char == '_' {
+                char
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    let name = name
+        .trim_start_matches(|char: char| !char.is_ascii_alphabetic())
+        .trim_end_matches(['-', '_']);
+
+    let name = if name.is_empty() {
+        format!("action{}", index + 1)
+    } else {
+        name.to_string()
+    };
+
+    // Ensure the ID is unique
+    let mut action_id = ActionId(name.clone());
+    let mut suffix = 2;
+    while actions.contains_key(&action_id) {
+        action_id = ActionId(format!("{name}{suffix}"));
+        suffix += 1;
+    }
+
+    action_id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"{
+        "UUID": "com.elgato.counter",
+        "Name": "Counter",
+        "Version": "1.2.0.0",
+        "Author": "Elgato",
+        "Description": "Count things",
+        "Icon": "imgs/plugin/icon",
+        "Category": "Counter",
+        "CategoryIcon": "imgs/plugin/category",
+        "CodePath": "bin/plugin.js",
+        "Nodejs": { "Version": "20", "Debug": "enabled" },
+        "SDKVersion": 2,
+        "Software": { "MinimumVersion": "6.4" },
+        "OS": [{ "Platform": "mac", "MinimumVersion": "12" }],
+        "Actions": [
+            {
+                "UUID": "com.elgato.counter.increment",
+                "Name": "Increment",
+                "Icon": "imgs/actions/increment.svg",
+                "Tooltip": "Increment the counter",
+                "PropertyInspectorPath": "ui/increment.html",
+                "States": [{ "Image": "imgs/actions/state", "TitleAlignment": "middle" }],
+                "Controllers": ["Keypad"]
+            },
+            {
+                "UUID": "com.elgato.counter.toggle.mode",
+                "Name": "Toggle",
+                "States": [{ "Image": "imgs/actions/off" }, { "Image": "imgs/actions/on" }]
+            }
+        ]
+    }"#;
+
+    fn paths(notes: &[ImportNote]) -> Vec<&str> {
+        notes.iter().map(|note| note.path.as_str()).collect()
+    }
+
+    #[test]
+    fn test_import_node_plugin() {
+        let (manifest, report) = import_stream_deck(MANIFEST).unwrap();
+
+        assert_eq!(manifest.plugin.id.as_str(), "com.elgato.counter");
+        assert_eq!(manifest.plugin.version, ManifestVersion::new(1, 2, 0));
+        assert_eq!(manifest.plugin.authors, ["Elgato"]);
+        assert_eq!(
+            manifest.plugin.icon.as_ref().map(ManifestPath::as_str),
+            Some("imgs/plugin/icon.png")
+        );
+        assert_eq!(manifest.category.label, "Counter");
+
+        let Some(MBin::Node { node }) = &manifest.bin else {
+            panic!("expected node binary");
+        };
+        assert_eq!(node.entrypoint.as_str(), "bin/plugin.js");
+
+        let keys: Vec<&str> = manifest.actions.0.keys().map(ActionId::as_str).collect();
+        assert_eq!(keys, ["increment", "toggle_mode"]);
+
+        let increment = &manifest.actions.0[0];
+        assert_eq!(
+            increment.description.as_deref(),
+            Some("Increment the counter")
+        );
+        assert_eq!(
+            increment.inspector.as_ref().map(ManifestPath::as_str),
+            Some("ui/increment.html")
+        );
+
+        let toggle = &manifest.actions.0[1];
+        assert_eq!(
+            toggle.icon.as_ref().map(ManifestPath::as_str),
+            Some("imgs/actions/off.png")
+        );
+
+        assert_eq!(
+            paths(&report.unmapped),
+            [
+                "Nodejs.Debug",
+                "Actions[0].States[0].TitleAlignment",
+                "Actions[0].Controllers",
+                "Actions[1].States",
+                "SDKVersion",
+                "Software",
+            ]
+        );
+        assert_eq!(
+            paths(&report.adjusted),
+            [
+                "Version",
+                "Icon",
+                "CategoryIcon",
+                "Actions[1].UUID",
+                "Actions[1].States[0].Image"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_native_plugin() {
+        let source = r#"{
+            "UUID": "com.example.native",
+            "Name": "Native",
+            "Version": "1.0.0",
+            "CodePathWin": "bin/plugin.exe",
+            "CodePath": "bin/plugin",
+            "Actions": []
+        }"#;
+
+        let (manifest, report) = import_stream_deck(source).unwrap();
+        let Some(MBin::Native { native }) = &manifest.bin else {
+            panic!("expected native binary");
+        };
+
+        let targets: Vec<(OperatingSystem, Arch, &str)> = native
+            .iter()
+            .map(|bin| (bin.os, bin.arch, bin.path.as_str()))
+            .collect();
+        assert_eq!(
+            targets,
+            [
+                (OperatingSystem::Windows, Arch::X64, "bin/plugin.exe"),
+                (OperatingSystem::MacOs, Arch::X64, "bin/plugin"),
+                (OperatingSystem::MacOs, Arch::Arm64, "bin/plugin"),
+            ]
+        );
+        assert_eq!(paths(&report.adjusted), ["Category"]);
+        assert!(report.unmapped.is_empty());
+    }
+
+    #[test]
+    fn test_import_html_plugin_unmapped() {
+        let source = r#"{
+            "UUID": "com.example.html",
+            "Name": "Html",
+            "Version": "1.0.0",
+            "Category": "Html",
+            "CodePath": "index.html",
+            "Actions": []
+        }"#;
+
+        let (manifest, report) = import_stream_deck(source).unwrap();
+        assert!(manifest.bin.is_none());
+        assert_eq!(paths(&report.unmapped), ["CodePath"]);
+    }
+
+    #[test]
+    fn test_import_non_numeric_version() {
+        let source = MANIFEST.replace("1.2.0.0", "1.2.beta");
+        let (manifest, report) = import_stream_deck(&source).unwrap();
+
+        assert_eq!(manifest.plugin.version, ManifestVersion::new(1, 2, 0));
+        let messages: Vec<&str> = report
+            .adjusted
+            .iter()
+            .filter(|note| note.path == "Version")
+            .map(|note| note.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "version part \"beta\" is not a number, replaced with 0",
+                "converted version \"1.2.beta\" to \"1.2.0\""
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_invalid_uuid_rejected() {
+        let source = MANIFEST.replace("com.elgato.counter", "1com.elgato.counter");
+        assert!(matches!(
+            import_stream_deck(&source),
+            Err(ManifestError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,309 @@
+//! # TypeScript
+//!
+//! Generation of TypeScript definitions (.d.ts) for the manifests. The
+//! definitions are generated from the manifest JSON schemas so they stay
+//! in sync with the schema, including the doc comments
+
+use crate::schema::{SchemaDraft, icons_schema_with, plugin_schema_with};
+use schemars::Schema;
+use serde_json::{Map, Value};
+use std::fmt::Write;
+
+/// Generate TypeScript definitions for a plugin manifest
+pub fn plugin_typescript() -> String {
+    schema_typescript(&plugin_schema_with(SchemaDraft::Draft07))
+}
+
+/// Generate TypeScript definitions for an icon pack manifest
+pub fn icons_typescript() -> String {
+    schema_typescript(&icons_schema_with(SchemaDraft::Draft07))
+}
+
+/// Generate TypeScript definitions for a draft 07 root `schema`, the
+/// root schema and each of its definitions are exported by name
+pub fn schema_typescript(schema: &Schema) -> String {
+    let root = schema.as_value();
+    let mut output = String::from("// Generated by tilepad-manifest, do not edit\n");
+
+    let name = root
+        .get("title")
+        .and_then(Value::as_str)
+        .unwrap_or("Manifest");
+    write_declaration(&mut output, name, root);
+
+    if let Some(definitions) = root.get("definitions").and_then(Value::as_object) {
+        for (name, schema) in definitions {
+            write_declaration(&mut output, name, schema);
+        }
+    }
+
+    output
+}
+
+/// Write an exported declaration for the schema
+fn write_declaration(output: &mut String, name: &str, schema: &Value) {
+    output.push('\n');
+    write_doc(output, schema, "");
+
+    match schema.get("properties").and_then(Value::as_object) {
+        Some(properties) if !has_branches(schema) => {
+            _ = writeln!(output, "export interface {name} {{");
+            write_properties(output, schema, properties, "  ");
+            output.push_str("}\n");
+        }
+        _ => {
+            _ = writeln!(output, "export type {name} = {};", type_of(schema, ""));
+        }
+    }
+}
+
+/// Write the properties of an object schema
+fn write_properties(
+    output: &mut String,
+    schema: &Value,
+    properties: &Map<String, Value>,
+    indent: &str,
+) {
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    for (name, property) in properties {
+        write_doc(output, property, indent);
+
+        let optional = if required.contains(&name.as_str()) {
+            ""
+        } else {
+            "?"
+        };
+
+        _ = writeln!(
+            output,
+            "{indent}{}{optional}: {};",
+            property_name(name),
+            type_of(property, indent)
+        );
+    }
+}
+
+/// Write the description of a schema as a doc comment
+fn write_doc(output: &mut String, schema: &Value, indent: &str) {
+    let Some(description) = schema.get("description").and_then(Value::as_str) else {
+        return;
+    };
+
+    // Prevent descriptions from closing the comment early
+    let description = description.replace("*/", "*\\/");
+
+    _ = writeln!(output, "{indent}/**");
+    for line in description.lines() {
+        if line.is_empty() {
+            _ = writeln!(output, "{indent} *");
+        } else {
+            _ = writeln!(output, "{indent} * {line}");
+        }
+    }
+
+    if let Some(default) = schema.get("default") {
+        _ = writeln!(output, "{indent} *");
+        _ = writeln!(output, "{indent} * @default {default}");
+    }
+
+    _ = writeln!(output, "{indent} */");
+}
+
+/// Get the TypeScript type for a schema
+fn type_of(schema: &Value, indent: &str) -> String {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return reference
+            .rsplit('/')
+            .next()
+            .unwrap_or(reference)
+            .to_string();
+    }
+
+    if let Some(value) = schema.get("const") {
+        return value.to_string();
+    }
+
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return union(values.iter().map(Value::to_string).collect());
+    }
+
+    for key in ["allOf", "anyOf", "oneOf"] {
+        if let Some(branches) = schema.get(key).and_then(Value::as_array) {
+            let types = branches
+                .iter()
+                .map(|branch| type_of(branch, indent))
+                .collect();
+            let combined = match key {
+                "allOf" => intersection(types),
+                _ => union(types),
+            };
+
+            // Properties declared alongside the branches apply to every branch
+            if schema.get("properties").is_some() {
+                return intersection(vec![object_type(schema, indent), combined]);
+            }
+
+            return combined;
+        }
+    }
+
+    match schema.get("type") {
+        Some(Value::Array(types)) => union(
+            types
+                .iter()
+                .map(|ty| primitive_type(schema, ty.as_str().unwrap_or_default(), indent))
+                .collect(),
+        ),
+        Some(Value::String(ty)) => primitive_type(schema, ty, indent),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Check whether the schema has "allOf", "anyOf" or "oneOf" branches
+fn has_branches(schema: &Value) -> bool {
+    ["allOf", "anyOf", "oneOf"]
+        .into_iter()
+        .any(|key| schema.get(key).is_some())
+}
+
+/// Get the TypeScript type for a schema "type"
+fn primitive_type(schema: &Value, ty: &str, indent: &str) -> String {
+    match ty {
+        "string" => "string".to_string(),
+        "integer" | "number" => "number".to_string(),
+        "boolean" => "boolean".to_string(),
+        "null" => "null".to_string(),
+        "array" => {
+            let items = schema
+                .get("items")
+                .map(|items| type_of(items, indent))
+                .unwrap_or_else(|| "unknown".to_string());
+
+            if items.contains(['|', '&']) {
+                format!("({items})[]")
+            } else {
+                format!("{items}[]")
+            }
+        }
+        "object" => object_type(schema, indent),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Get the TypeScript type for an inline object schema
+fn object_type(schema: &Value, indent: &str) -> String {
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        let inner = format!("{indent}  ");
+        let mut output = String::from("{\n");
+        write_properties(&mut output, schema, properties, &inner);
+        output.push_str(indent);
+        output.push('}');
+        return output;
+    }
+
+    match schema.get("additionalProperties") {
+        Some(additional) if additional.is_object() => {
+            format!("Record<string, {}>", type_of(additional, indent))
+        }
+        _ => "Record<string, unknown>".to_string(),
+    }
+}
+
+/// Join types into a union, duplicate types are removed
+fn union(mut types: Vec<String>) -> String {
+    dedup(&mut types);
+    types.join(" | ")
+}
+
+/// Join types into an intersection, duplicate types are removed
+fn intersection(mut types: Vec<String>) -> String {
+    dedup(&mut types);
+    types
+        .into_iter()
+        .map(|ty| {
+            if ty.contains('|') {
+                format!("({ty})")
+            } else {
+                ty
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" & ")
+}
+
+/// Remove duplicate types while preserving order
+fn dedup(types: &mut Vec<String>) {
+    let mut seen = Vec::with_capacity(types.len());
+    types.retain(|ty| {
+        if seen.contains(ty) {
+            false
+        } else {
+            seen.push(ty.clone());
+            true
+        }
+    });
+}
+
+/// Quote property names that are not valid identifiers
+fn property_name(name: &str) -> String {
+    let valid = name
+        .chars()
+        .next()
+        .is_some_and(|char| char.is_ascii_alphabetic() || char == '_' || char == '$')
+        && name
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '_' || char == '$');
+
+    if valid {
+        name.to_string()
+    } else {
+        format!("{name:?}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_typescript() {
+        let output = plugin_typescript();
+
+        assert!(output.contains("export interface PluginManifest {"));
+        assert!(output.contains("  plugin: MPlugin;"));
+        assert!(output.contains("  bin?: MBin | null;"));
+        assert!(output.contains("export type ActionMap = Record<string, ManifestAction>;"));
+        assert!(output.contains("export type PluginId = string;"));
+        assert!(
+            output.contains("export type OperatingSystem = \"windows\" | \"macos\" | \"linux\";")
+        );
+        assert!(output.contains("  background_color?: Color | null;"));
+        assert!(output.contains("  padding?: number | null;"));
+        assert!(output.contains("   * Color of the tile border\n"));
+        assert!(output.contains("  native: MBinNative[];"));
+        assert!(output.contains("export type SettingField = ({\n"));
+        assert!(output.contains("}) & ({\n"));
+        assert!(output.contains("  type: \"enum\";"));
+        assert!(output.contains("  settings?: SettingsSchema | null;"));
+    }
+
+    #[test]
+    fn test_icons_typescript() {
+        let output = icons_typescript();
+
+        assert!(output.contains("export interface IconsManifest {"));
+        assert!(output.contains("export type IconList = Icon[];"));
+        assert!(output.contains("  tags?: string[];"));
+    }
+
+    #[test]
+    fn test_property_name() {
+        assert_eq!(property_name("label"), "label");
+        assert_eq!(property_name("my-field"), "\"my-field\"");
+    }
+}
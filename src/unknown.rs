@@ -0,0 +1,522 @@
+//! # Unknown
+//!
+//! Detection of unknown fields within manifests. Unknown fields are
+//! ignored when deserializing so typos (i.e "inspecter") would otherwise
+//! go unnoticed, fields are checked against the manifest JSON schema and
+//! the closest known field is suggested
+//!
+//! Fields that are accepted when deserializing but are not part of the
+//! schema properties are declared within the schema itself, serde aliases
+//! under "x-aliases" on the property and fields skipped from the schema
+//! under "x-undocumented" on the object
+
+use crate::{
+    ManifestError,
+    format::ManifestFormat,
+    schema::{SchemaDraft, schema_for},
+};
+use garde::Path;
+use schemars::JsonSchema;
+use serde_json::{Map, Value};
+use std::fmt::Display;
+
+/// Field present in a manifest that is not known
+#[derive(Debug, Clone)]
+pub struct UnknownField {
+    /// Path to the unknown field (i.e actions.my_action.inspecter)
+    pub path: Path,
+    /// Name of the unknown field
+    pub field: String,
+    /// Closest known field name, if one is similar enough
+    pub suggestion: Option<String>,
+}
+
+impl Display for UnknownField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown field \"{}\"", self.field)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, ", did you mean \"{suggestion}\"?")?;
+        }
+        Ok(())
+    }
+}
+
+/// Find all the unknown fields within the manifest `source` for
+/// the manifest type `M`
+pub fn unknown_fields<M>(
+    source: &str,
+    format: ManifestFormat,
+) -> Result<Vec<UnknownField>, ManifestError>
+where
+    M: JsonSchema,
+{
+    let value: Value = format.deserialize(source)?;
+    Ok(find_unknown_fields::<M>(&value))
+}
+
+/// Find all the unknown fields within the manifest `value` for
+/// the manifest type `M`
+pub fn find_unknown_fields<M>(value: &Value) -> Vec<UnknownField>
+where
+    M: JsonSchema,
+{
+    // Definitions are resolved from the draft 07 "definitions"
+    let schema = schema_for::<M>(SchemaDraft::Draft07);
+    let root = schema.as_value();
+
+    let walker = Walker {
+        definitions: root.get("definitions").and_then(Value::as_object),
+    };
+
+    let mut fields = Vec::new();
+    walker.walk(value, root, Path::empty(), &mut fields);
+    fields
+}
+
+/// Walks a value alongside its schema
+struct Walker<'a> {
+    /// Schema definitions to resolve references from
+    definitions: Option<&'a Map<String, Value>>,
+}
+
+impl<'a> Walker<'a> {
+    fn walk<'s>(&self, value: &Value, schema: &'s Value, path: Path, fields: &mut Vec<UnknownField>)
+    where
+        'a: 's,
+    {
+        let schema = self.resolve(schema);
+
+        // Choose the branch that best matches the value
+        let branches = self.branches(schema);
+        if !branches.is_empty() {
+            let branch = branches
+                .iter()
+                .filter(|branch| matches_type(branch, value) && matches_const(branch, value))
+                .max_by_key(|branch| match value {
+                    Value::Object(object) => object
+                        .keys()
+                        .filter(|key| property(branch, key).is_some())
+                        .count(),
+                    _ => 0,
+                });
+
+            if let Some(branch) = branch {
+                match merge_properties(schema, branch) {
+                    Some(merged) => self.walk(value, &merged, path, fields),
+                    None => self.walk(value, branch, path, fields),
+                }
+            }
+            return;
+        }
+
+        match value {
+            Value::Object(object) => {
+                let properties = schema.get("properties").and_then(Value::as_object);
+                let additional = schema
+                    .get("additionalProperties")
+                    .filter(|value| value.is_object());
+
+                for (key, value) in object {
+                    let key_path = path.join(key.as_str());
+
+                    if let Some(property) = property(schema, key) {
+                        self.walk(value, property, key_path, fields);
+                    } else if let Some(additional) = additional {
+                        self.walk(value, additional, key_path, fields);
+                    } else if let Some(properties) = properties {
+                        if is_undocumented(schema, key) {
+                            continue;
+                        }
+
+                        fields.push(UnknownField {
+                            path: key_path,
+                            field: key.clone(),
+                            suggestion: suggest(key, properties.keys().map(String::as_str))
+                                .map(str::to_string),
+                        });
+                    }
+                }
+            }
+            Value::Array(array) => {
+                if let Some(items) = schema.get("items") {
+                    for (index, value) in array.iter().enumerate() {
+                        self.walk(value, items, path.join(index), fields);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Resolve a schema reference
+    fn resolve<'s>(&self, schema: &'s Value) -> &'s Value
+    where
+        'a: 's,
+    {
+        let reference = schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix("#/definitions/"));
+
+        match reference {
+            Some(reference) => match self
+                .definitions
+                .and_then(|definitions| definitions.get(reference))
+            {
+                Some(schema) => self.resolve(schema),
+                None => schema,
+            },
+            None => schema,
+        }
+    }
+
+    /// Collect the resolved branches of an "allOf", "anyOf" or "oneOf" schema
+    fn branches<'s>(&self, schema: &'s Value) -> Vec<&'s Value>
+    where
+        'a: 's,
+    {
+        ["allOf", "anyOf", "oneOf"]
+            .into_iter()
+            .filter_map(|key| schema.get(key).and_then(Value::as_array))
+            .flatten()
+            .map(|branch| self.resolve(branch))
+            .collect()
+    }
+}
+
+/// Get the schema for a property within an object schema, properties
+/// can also be found using any of their "x-aliases"
+fn property<'a>(schema: &'a Value, key: &str) -> Option<&'a Value> {
+    let properties = schema.get("properties")?.as_object()?;
+    properties.get(key).or_else(|| {
+        properties
+            .values()
+            .find(|property| contains_str(property.get("x-aliases"), key))
+    })
+}
+
+/// Check whether the object schema declares `key` as an "x-undocumented"
+/// field that is accepted but not part of its properties
+fn is_undocumented(schema: &Value, key: &str) -> bool {
+    contains_str(schema.get("x-undocumented"), key)
+}
+
+/// Check whether `value` is an array containing the string `key`
+fn contains_str(value: Option<&Value>, key: &str) -> bool {
+    value
+        .and_then(Value::as_array)
+        .is_some_and(|values| values.iter().any(|value| value.as_str() == Some(key)))
+}
+
+/// Merge the properties declared alongside the branches of a schema (i.e
+/// fields next to a flattened enum) into the chosen `branch`
+fn merge_properties(schema: &Value, branch: &Value) -> Option<Value> {
+    let properties = schema.get("properties").and_then(Value::as_object)?;
+
+    let mut merged = branch.clone();
+    let merged_properties = merged
+        .as_object_mut()?
+        .entry("properties")
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()?;
+
+    for (key, property) in properties {
+        merged_properties
+            .entry(key.clone())
+            .or_insert_with(|| property.clone());
+    }
+
+    Some(merged)
+}
+
+/// Check whether the constant properties of the schema (i.e enum tags)
+/// match the value, values missing the property match anything
+fn matches_const(schema: &Value, value: &Value) -> bool {
+    let (Some(properties), Some(object)) = (
+        schema.get("properties").and_then(Value::as_object),
+        value.as_object(),
+    ) else {
+        return true;
+    };
+
+    properties.iter().all(
+        |(key, property)| match (property.get("const"), object.get(key)) {
+            (Some(expected), Some(actual)) => expected == actual,
+            _ => true,
+        },
+    )
+}
+
+/// Check whether the schema could describe the value based on
+/// its declared type, schemas without a type match anything
+fn matches_type(schema: &Value, value: &Value) -> bool {
+    let name = match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    };
+
+    let matches = |ty: &Value| {
+        ty.as_str()
+            .is_some_and(|ty| ty == name || (ty == "integer" && (value.is_i64() || value.is_u64())))
+    };
+
+    match schema.get("type") {
+        Some(Value::Array(types)) => types.iter().any(matches),
+        Some(ty) => matches(ty),
+        None => true,
+    }
+}
+
+/// Find the closest candidate to `field`, only candidates that
+/// are similar enough to be a likely typo are suggested
+fn suggest<'a>(field: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .map(|candidate| (candidate, distance(field, candidate)))
+        .filter(|(candidate, distance)| {
+            let length = field.chars().count().max(candidate.chars().count());
+            *distance <= (length / 3).max(1)
+        })
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
+
+/// Levenshtein distance between two strings
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let cost = usize::from(a != *b);
+            current[j + 1] = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{icons::IconsManifest, plugin::PluginManifest};
+
+    fn fields<M: JsonSchema>(source: &str) -> Vec<(String, Option<String>)> {
+        unknown_fields::<M>(source, ManifestFormat::Json)
+            .unwrap()
+            .into_iter()
+            .map(|field| (field.path.to_string(), field.suggestion))
+            .collect()
+    }
+
+    #[test]
+    fn test_unknown_action_fields() {
+        let source = r#"{
+            "manifest_version": 2,
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "0.1.0", "authors": [], "internal": true },
+            "category": { "label": "Test" },
+            "actions": {
+                "example": {
+                    "label": "Example",
+                    "inspecter": "inspector/index.html",
+                    "icon_option": { "padding": 4, "colour": "red" },
+                    "something_else": true
+                }
+            }
+        }"#;
+
+        assert_eq!(
+            fields::<PluginManifest>(source),
+            [
+                (
+                    "actions.example.inspecter".to_string(),
+                    Some("inspector".to_string())
+                ),
+                (
+                    "actions.example.icon_option".to_string(),
+                    Some("icon_options".to_string())
+                ),
+                ("actions.example.something_else".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_fields_within_options() {
+        let source = r#"{
+            "manifest_version": 2,
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "0.1.0", "authors": [] },
+            "bin": { "native": [{ "os": "linux", "arch": "x64", "path": "bin/program", "args": [] }] },
+            "category": { "label": "Test", "icn": "images/icon.svg" },
+            "actions": {
+                "example": { "label": "Example", "icon_options": { "paddin": 4 } }
+            }
+        }"#;
+
+        assert_eq!(
+            fields::<PluginManifest>(source),
+            [
+                ("bin.native[0].args".to_string(), None),
+                ("category.icn".to_string(), Some("icon".to_string())),
+                (
+                    "actions.example.icon_options.paddin".to_string(),
+                    Some("padding".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_icon_fields() {
+        let source = r#"{
+            "manifest_version": 2,
+            "icons": { "id": "com.example.pack", "name": "Pack", "version": "0.1.0", "authors": [] },
+            "list": [
+                { "path": "icons/arrow.svg", "name": "Arrow", "keywords": ["direction"] },
+                { "path": "icons/circle.svg", "name": "Circle", "tag": ["shape"] }
+            ]
+        }"#;
+
+        assert_eq!(
+            fields::<IconsManifest>(source),
+            [("list[1].tag".to_string(), Some("tags".to_string()))]
+        );
+    }
+
+    #[test]
+    fn test_unknown_setting_fields() {
+        let source = r#"{
+            "manifest_version": 2,
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "0.1.0", "authors": [] },
+            "category": { "label": "Test" },
+            "actions": {
+                "example": {
+                    "label": "Example",
+                    "settings": {
+                        "speed": { "type": "number", "label": "Speed", "maxx": 10 },
+                        "mode": { "type": "enum", "labl": "Mode", "options": [{ "value": "a" }] }
+                    }
+                }
+            }
+        }"#;
+
+        assert_eq!(
+            fields::<PluginManifest>(source),
+            [
+                (
+                    "actions.example.settings.speed.maxx".to_string(),
+                    Some("max".to_string())
+                ),
+                (
+                    "actions.example.settings.mode.labl".to_string(),
+                    Some("label".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_aliases_and_skipped_fields_known() {
+        let source = r#"{
+            "manifest_version": 2,
+            "plugin": { "id": "com.example.test", "name": "Test", "version": "0.1.0", "authors": [], "internal": true },
+            "category": { "label": "Test" },
+            "actions": {}
+        }"#;
+        let manifest: PluginManifest = serde_json::from_str(source).unwrap();
+        assert_eq!(manifest.plugin.internal, Some(true));
+        assert!(fields::<PluginManifest>(source).is_empty());
+
+        let source = r#"{
+            "manifest_version": 2,
+            "icons": { "id": "com.example.pack", "name": "Pack", "version": "0.1.0", "authors": [] },
+            "list": [{ "path": "icons/arrow.svg", "name": "Arrow", "keywords": ["direction"] }]
+        }"#;
+        let manifest: IconsManifest = serde_json::from_str(source).unwrap();
+        assert_eq!(manifest.list.0[0].tags, ["direction"]);
+        assert!(fields::<IconsManifest>(source).is_empty());
+    }
+
+    /// Every serde alias and field skipped from the schema within the
+    /// manifest types must be declared within the schema
+    #[test]
+    fn test_aliases_and_skipped_fields_declared() {
+        let sources = [
+            include_str!("color.rs"),
+            include_str!("engines.rs"),
+            include_str!("icons.rs"),
+            include_str!("path.rs"),
+            include_str!("permissions.rs"),
+            include_str!("plugin.rs"),
+            include_str!("settings.rs"),
+            include_str!("system.rs"),
+            include_str!("version.rs"),
+        ];
+
+        let mut expected = Vec::new();
+        for source in sources {
+            let mut lines = source.lines().map(str::trim);
+            while let Some(line) = lines.next() {
+                if let Some((_, alias)) = line.split_once("alias = \"") {
+                    expected.extend(alias.split('"').next().map(str::to_string));
+                }
+                if line == "#[schemars(skip)]" {
+                    let field = lines.find_map(|line| line.strip_prefix("pub "));
+                    expected.extend(
+                        field
+                            .and_then(|field| field.split(':').next())
+                            .map(str::to_string),
+                    );
+                }
+            }
+        }
+        expected.sort();
+
+        let mut declared = Vec::new();
+        for schema in [
+            schema_for::<PluginManifest>(SchemaDraft::Draft07),
+            schema_for::<IconsManifest>(SchemaDraft::Draft07),
+        ] {
+            collect_declared(schema.as_value(), &mut declared);
+        }
+        declared.sort();
+        declared.dedup();
+
+        assert!(!expected.is_empty());
+        assert_eq!(declared, expected);
+    }
+
+    fn collect_declared(value: &Value, declared: &mut Vec<String>) {
+        match value {
+            Value::Object(object) => {
+                for (key, value) in object {
+                    if key == "x-aliases" || key == "x-undocumented" {
+                        let names = value.as_array().into_iter().flatten();
+                        declared.extend(names.filter_map(Value::as_str).map(str::to_string));
+                    } else {
+                        collect_declared(value, declared);
+                    }
+                }
+            }
+            Value::Array(array) => array
+                .iter()
+                .for_each(|value| collect_declared(value, declared)),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("inspecter", "inspector"), 1);
+        assert_eq!(distance("", "abc"), 3);
+        assert_eq!(distance("kitten", "sitting"), 3);
+    }
+}
@@ -1,12 +1,50 @@
-use crate::plugin::{ActionId, ActionMap};
+use crate::{
+    color::{Color, ColorMode},
+    icons::IconList,
+    migration::CURRENT_MANIFEST_VERSION,
+    permissions::FilesystemPermissions,
+    plugin::{ActionId, ActionMap, BinaryKind, MBin},
+    settings::{SettingField, SettingKey, SettingKind, SettingsSchema},
+    version::ManifestVersion,
+};
 use garde::{
     Path, Report, Validate,
     error::{Kind, PathComponentKind},
 };
+use std::collections::HashMap;
 
 /// Separators allowed in names
 static NAME_SEPARATORS: [char; 2] = ['-', '_'];
 
+/// Options used as the context when validating manifests, allows
+/// hosts to tune how strict validation should be
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationOptions {
+    /// Mode determining which colors are accepted
+    pub color_mode: ColorMode,
+    /// Maximum number of characters allowed in text fields (names,
+    /// labels, descriptions, authors), [None] for no limit
+    pub max_text_length: Option<usize>,
+    /// Kinds of binaries plugins are allowed to use
+    pub allowed_binaries: Vec<BinaryKind>,
+    /// Whether plugins are allowed to mark themselves as internal
+    pub allow_internal: bool,
+    /// Whether unknown fields are rejected rather than ignored
+    pub deny_unknown_fields: bool,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self {
+            color_mode: ColorMode::Strict,
+            max_text_length: None,
+            allowed_binaries: vec![BinaryKind::Node, BinaryKind::Native],
+            allow_internal: true,
+            deny_unknown_fields: false,
+        }
+    }
+}
+
 /// Validate an ID (plugin ID or icon pack ID)
 pub fn validate_id(value: &str, _context: &()) -> garde::Result {
     let parts = value.split('.');
@@ -67,238 +105,497 @@ pub fn validate_name(value: &str, _context: &()) -> garde::Result {
     Ok(())
 }
 
-impl Validate for ActionMap {
-    type Context = ();
-
-    fn validate_into(&self, ctx: &(), mut parent: &mut dyn FnMut() -> Path, report: &mut Report) {
-        for (key, value) in self.0.iter() {
-            let mut path = garde::util::nested_path!(parent, key);
-            value.validate_into(ctx, &mut path, report);
-        }
+/// Validates that a text field does not exceed the maximum length
+pub fn validate_text(value: &str, options: &ValidationOptions) -> garde::Result {
+    if let Some(max_length) = options.max_text_length
+        && value.chars().count() > max_length
+    {
+        return Err(garde::Error::new(format!(
+            "length must not be greater than {max_length}"
+        )));
     }
-}
 
-impl PathComponentKind for ActionId {
-    fn component_kind() -> Kind {
-        Kind::Key
-    }
+    Ok(())
 }
 
-/// Validates that a string is a valid color value supports:
-/// - hex
-/// - rgb/rgba
-/// - hsl/hsla
-///
-/// Does not check for named colors, we don't really want those anyway
-/// as they aren't really useful
-pub fn validate_color(value: &str, _context: &()) -> garde::Result {
-    let value = value.trim().to_lowercase();
-
-    // Hex
-    if value.starts_with('#') {
-        return validate_hex_color(&value);
+/// Validates that the plugin binary is one of the allowed kinds
+pub fn validate_binary(value: &Option<MBin>, options: &ValidationOptions) -> garde::Result {
+    if let Some(bin) = value {
+        let kind = bin.kind();
+        if !options.allowed_binaries.contains(&kind) {
+            return Err(garde::Error::new(format!(
+                "{kind} binaries are not allowed"
+            )));
+        }
     }
 
-    // RGB
-    if value.starts_with("rgb(") {
-        return validate_rgb_color(&value);
-    }
+    Ok(())
+}
 
-    // RGBA
-    if value.starts_with("rgba(") {
-        return validate_rgba_color(&value);
+/// Validates that the manifest version is supported, older versions
+/// must be migrated before they can be used
+pub fn validate_manifest_version(value: &u32, _options: &ValidationOptions) -> garde::Result {
+    if *value == 0 {
+        return Err(garde::Error::new("manifest version must be at least 1"));
     }
 
-    // HSL
-    if value.starts_with("hsl(") {
-        return validate_hsl_color(&value);
+    if *value < CURRENT_MANIFEST_VERSION {
+        return Err(garde::Error::new(format!(
+            "manifest version {value} is outdated and must be migrated to version {CURRENT_MANIFEST_VERSION}"
+        )));
     }
 
-    // HSLA
-    if value.starts_with("hsla(") {
-        return validate_hsla_color(&value);
+    if *value > CURRENT_MANIFEST_VERSION {
+        return Err(garde::Error::new(format!(
+            "manifest version {value} is newer than the latest supported version {CURRENT_MANIFEST_VERSION}"
+        )));
     }
 
-    Err(garde::Error::new("invalid color value"))
+    Ok(())
 }
 
-/// Validate a hex color
-fn validate_hex_color(value: &str) -> garde::Result {
-    let value = value
-        .strip_prefix('#')
-        .ok_or_else(|| garde::Error::new("hex color must start with #"))?;
-
-    match value.len() {
-        3 | 4 | 6 | 8 => {}
-        _ => {
-            return Err(garde::Error::new(
-                "hex color must be 3, 4, 6, or 8 hex digits",
-            ));
-        }
+/// Validates that the plugin is only internal when internal plugins are allowed
+pub fn validate_internal(value: &Option<bool>, options: &ValidationOptions) -> garde::Result {
+    if value.unwrap_or_default() && !options.allow_internal {
+        return Err(garde::Error::new("internal plugins are not allowed"));
     }
 
-    if !value.chars().all(|c| c.is_ascii_hexdigit()) {
-        return Err(garde::Error::new("hex color contains invalid characters"));
+    Ok(())
+}
+
+/// Validates that action settings do not contain secrets, secrets
+/// are only supported within the plugin settings
+pub fn validate_action_settings(
+    value: &Option<SettingsSchema>,
+    _options: &ValidationOptions,
+) -> garde::Result {
+    if let Some(settings) = value
+        && let Some(key) = settings.secrets().next()
+    {
+        return Err(garde::Error::new(format!(
+            "secret settings are only supported in the plugin settings (\"{key}\" is secret)"
+        )));
     }
 
     Ok(())
 }
 
-/// Validate a rgb() color
-fn validate_rgb_color(value: &str) -> garde::Result {
-    // Strip opening
-    let value = value
-        .strip_prefix("rgb(")
-        .ok_or_else(|| garde::Error::new("rgb color must start with rgb("))?;
+/// Validates that a network host is a valid host name with an optional port
+/// (i.e api.example.com, localhost:8080), a leading wildcard segment matches
+/// subdomains (i.e *.example.com) and "*" matches any host
+pub fn validate_network_host(value: &str, _context: &()) -> garde::Result {
+    if value == "*" {
+        return Ok(());
+    }
 
-    // Strip closing
-    let value = value
-        .strip_suffix(")")
-        .ok_or_else(|| garde::Error::new("unclosed rgb color"))?;
+    if value.contains("://") || value.contains('/') {
+        return Err(garde::Error::new(
+            "host must not include a scheme or path (i.e api.example.com)",
+        ));
+    }
 
-    let parts: Vec<&str> = value.split(',').map(|s| s.trim()).collect();
+    let host = match value.rsplit_once(':') {
+        Some((host, port)) => {
+            if !port.parse::<u16>().is_ok_and(|port| port != 0) {
+                return Err(garde::Error::new(format!("invalid port \"{port}\"")));
+            }
+            host
+        }
+        None => value,
+    };
 
-    if parts.len() != 3 {
-        return Err(garde::Error::new("invalid rgb color"));
+    let host = host.strip_prefix("*.").unwrap_or(host);
+    if host.is_empty() {
+        return Err(garde::Error::new("host must not be empty"));
     }
 
-    for part in parts {
-        parse_rgb_component(part)?;
+    for label in host.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(garde::Error::new(
+                "host segments must be between 1 and 63 characters",
+            ));
+        }
+
+        // Must only contain a-zA-Z0-9- (wildcards are only allowed
+        // as the first segment which has already been stripped)
+        if !label
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '-')
+        {
+            return Err(garde::Error::new(
+                "host segments must only contain ascii alpha numeric values and -",
+            ));
+        }
+
+        if label.starts_with('-') || label.ends_with('-') {
+            return Err(garde::Error::new(
+                "host segments must not start or end with -",
+            ));
+        }
     }
 
     Ok(())
 }
 
-/// Validate a rgba() color
-fn validate_rgba_color(value: &str) -> garde::Result {
-    // Strip opening
-    let value = value
-        .strip_prefix("rgba(")
-        .ok_or_else(|| garde::Error::new("rgba color must start with rgba("))?;
-
-    // Strip closing
-    let value = value
-        .strip_suffix(")")
-        .ok_or_else(|| garde::Error::new("unclosed rgba color"))?;
+/// Validates that a path is a safe relative path within the bundle
+pub fn validate_path(value: &str, _context: &()) -> garde::Result {
+    if value.is_empty() {
+        return Err(garde::Error::new("path must not be empty"));
+    }
 
-    let parts: Vec<&str> = value.split(',').map(|s| s.trim()).collect();
+    // Only forward slashes are allowed as separators
+    if value.contains('\\') {
+        return Err(garde::Error::new(
+            "path must use forward slashes (/) as separators",
+        ));
+    }
 
-    if parts.len() != 4 {
-        return Err(garde::Error::new("invalid rgba color"));
+    // Must not be absolute
+    if value.starts_with('/') {
+        return Err(garde::Error::new("path must be relative"));
     }
 
-    // RGB components
-    for part in &parts[..3] {
-        parse_rgb_component(part)?;
+    // Must not contain windows drive or stream prefixes (i.e C:)
+    if value.contains(':') {
+        return Err(garde::Error::new("path must not contain :"));
     }
 
-    // Alpha component
-    parse_alpha(parts[3])?;
+    for segment in value.split('/') {
+        // Must not contain empty segments (i.e a//b or a/)
+        if segment.is_empty() {
+            return Err(garde::Error::new("path must not contain empty segments"));
+        }
+
+        // Must not escape the bundle
+        if segment == ".." {
+            return Err(garde::Error::new("path must not contain .. segments"));
+        }
+    }
 
     Ok(())
 }
 
-/// Validate a hsl() color
-fn validate_hsl_color(value: &str) -> garde::Result {
-    // Strip opening
-    let value = value
-        .strip_prefix("hsl(")
-        .ok_or_else(|| garde::Error::new("hsl color must start with hsl("))?;
+impl Validate for ActionMap {
+    type Context = ValidationOptions;
+
+    fn validate_into(
+        &self,
+        ctx: &ValidationOptions,
+        mut parent: &mut dyn FnMut() -> Path,
+        report: &mut Report,
+    ) {
+        for (key, value) in self.0.iter() {
+            let mut path = garde::util::nested_path!(parent, key);
+            value.validate_into(ctx, &mut path, report);
+        }
+    }
+}
 
-    // Strip closing
-    let value = value
-        .strip_suffix(")")
-        .ok_or_else(|| garde::Error::new("unclosed hsl color"))?;
+impl Validate for IconList {
+    type Context = ValidationOptions;
+
+    fn validate_into(
+        &self,
+        ctx: &ValidationOptions,
+        mut parent: &mut dyn FnMut() -> Path,
+        report: &mut Report,
+    ) {
+        // Index of the first icon using each name
+        let mut names: HashMap<&str, usize> = HashMap::with_capacity(self.0.len());
+
+        for (index, icon) in self.0.iter().enumerate() {
+            let mut path = garde::util::nested_path!(parent, index);
+            icon.validate_into(ctx, &mut path, report);
+
+            // Names must be unique across the list
+            if let Some(&existing) = names.get(icon.name.as_str()) {
+                let mut path = garde::util::nested_path!(path, "name");
+                report.append(
+                    path(),
+                    garde::Error::new(format!(
+                        "duplicate icon name \"{}\" (already used by icon #{})",
+                        icon.name,
+                        existing + 1
+                    )),
+                );
+            } else {
+                names.insert(icon.name.as_str(), index);
+            }
+        }
+    }
+}
 
-    let parts: Vec<&str> = value.split(',').map(|s| s.trim()).collect();
+impl Validate for FilesystemPermissions {
+    type Context = ValidationOptions;
+
+    fn validate_into(
+        &self,
+        _ctx: &ValidationOptions,
+        mut parent: &mut dyn FnMut() -> Path,
+        report: &mut Report,
+    ) {
+        for (index, permission) in self.0.iter().enumerate() {
+            // Scopes must be unique across the list
+            if let Some(existing) = self.0[..index]
+                .iter()
+                .position(|other| other.scope == permission.scope)
+            {
+                let mut path = garde::util::nested_path!(parent, index);
+                report.append(
+                    path(),
+                    garde::Error::new(format!(
+                        "duplicate filesystem scope \"{}\" (already requested by permission #{})",
+                        permission.scope,
+                        existing + 1
+                    )),
+                );
+            }
+        }
+    }
+}
 
-    if parts.len() != 3 {
-        return Err(garde::Error::new("invalid hsl color"));
+impl PathComponentKind for ActionId {
+    fn component_kind() -> Kind {
+        Kind::Key
     }
+}
 
-    parse_hue(parts[0])?;
-    parse_percentage(parts[1])?;
-    parse_percentage(parts[2])?;
+impl Validate for SettingsSchema {
+    type Context = ValidationOptions;
 
-    Ok(())
+    fn validate_into(
+        &self,
+        ctx: &ValidationOptions,
+        mut parent: &mut dyn FnMut() -> Path,
+        report: &mut Report,
+    ) {
+        for (key, value) in self.0.iter() {
+            let mut path = garde::util::nested_path!(parent, key);
+            key.validate_into(&(), &mut path, report);
+            value.validate_into(ctx, &mut path, report);
+        }
+    }
 }
 
-/// Validate a hsla() color
-fn validate_hsla_color(value: &str) -> garde::Result {
-    // Strip opening
-    let value = value
-        .strip_prefix("hsla(")
-        .ok_or_else(|| garde::Error::new("hsla color must start with hsla("))?;
-
-    // Strip closing
-    let value = value
-        .strip_suffix(")")
-        .ok_or_else(|| garde::Error::new("unclosed hsla color"))?;
+impl PathComponentKind for SettingKey {
+    fn component_kind() -> Kind {
+        Kind::Key
+    }
+}
 
-    let parts: Vec<&str> = value.split(',').map(|s| s.trim()).collect();
+impl Validate for SettingField {
+    type Context = ValidationOptions;
+
+    fn validate_into(
+        &self,
+        ctx: &ValidationOptions,
+        mut parent: &mut dyn FnMut() -> Path,
+        report: &mut Report,
+    ) {
+        if let Some(label) = &self.label {
+            let mut path = garde::util::nested_path!(parent, "label");
+            if label.is_empty() {
+                report.append(path(), garde::Error::new("length is lower than 1"));
+            }
+            if let Err(error) = validate_text(label, ctx) {
+                report.append(path(), error);
+            }
+        }
 
-    if parts.len() != 4 {
-        return Err(garde::Error::new("invalid hsla color"));
-    }
+        if let Some(description) = &self.description
+            && let Err(error) = validate_text(description, ctx)
+        {
+            let mut path = garde::util::nested_path!(parent, "description");
+            report.append(path(), error);
+        }
 
-    parse_hue(parts[0])?;
-    parse_percentage(parts[1])?;
-    parse_percentage(parts[2])?;
-    parse_alpha(parts[3])?;
+        if let Some(placeholder) = &self.placeholder
+            && let Err(error) = validate_text(placeholder, ctx)
+        {
+            let mut path = garde::util::nested_path!(parent, "placeholder");
+            report.append(path(), error);
+        }
 
-    Ok(())
-}
+        if self.is_secret() {
+            if !matches!(self.kind, SettingKind::String { .. }) {
+                let mut path = garde::util::nested_path!(parent, "secret");
+                report.append(
+                    path(),
+                    garde::Error::new("only string settings can be secret"),
+                );
+            }
+
+            // Defaults are visible to anyone reading the manifest
+            if self.default_value().is_some() {
+                let mut path = garde::util::nested_path!(parent, "default");
+                report.append(
+                    path(),
+                    garde::Error::new("secret settings must not have a default value"),
+                );
+            }
+        }
 
-/// Parse an RGB component (0–255 or 0–100%)
-fn parse_rgb_component(s: &str) -> garde::Result {
-    if s.ends_with('%') {
-        return parse_percentage(s);
-    }
+        match &self.kind {
+            SettingKind::String {
+                min_length: Some(min_length),
+                max_length: Some(max_length),
+                ..
+            } if max_length < min_length => {
+                let mut path = garde::util::nested_path!(parent, "max_length");
+                report.append(
+                    path(),
+                    garde::Error::new("max_length must not be less than min_length"),
+                );
+            }
+            SettingKind::Number { min, max, step, .. } => {
+                for (name, value) in [("min", min), ("max", max), ("step", step)] {
+                    if let Some(value) = value
+                        && !value.is_finite()
+                    {
+                        let mut path = garde::util::nested_path!(parent, name);
+                        report.append(
+                            path(),
+                            garde::Error::new(format!("{name} must be a finite number")),
+                        );
+                    }
+                }
+
+                if let (Some(min), Some(max)) = (min, max)
+                    && max < min
+                {
+                    let mut path = garde::util::nested_path!(parent, "max");
+                    report.append(path(), garde::Error::new("max must not be less than min"));
+                }
+
+                if let Some(step) = step
+                    && *step <= 0.0
+                {
+                    let mut path = garde::util::nested_path!(parent, "step");
+                    report.append(path(), garde::Error::new("step must be greater than 0"));
+                }
+            }
+            SettingKind::Enum { options, .. } => {
+                let mut options_path = garde::util::nested_path!(parent, "options");
+                if options.is_empty() {
+                    report.append(
+                        options_path(),
+                        garde::Error::new("at least one option is required"),
+                    );
+                }
+
+                for (index, option) in options.iter().enumerate() {
+                    let mut path = garde::util::nested_path!(options_path, index);
+
+                    if options[..index]
+                        .iter()
+                        .any(|other| other.value == option.value)
+                    {
+                        let mut path = garde::util::nested_path!(path, "value");
+                        report.append(
+                            path(),
+                            garde::Error::new(format!(
+                                "duplicate option value \"{}\"",
+                                option.value
+                            )),
+                        );
+                    }
+
+                    if let Some(label) = &option.label
+                        && let Err(error) = validate_text(label, ctx)
+                    {
+                        let mut path = garde::util::nested_path!(path, "label");
+                        report.append(path(), error);
+                    }
+                }
+            }
+            SettingKind::Color {
+                default: Some(default),
+            } => {
+                let mut path = garde::util::nested_path!(parent, "default");
+                default.validate_into(ctx, &mut path, report);
+            }
+            SettingKind::File { extensions } => {
+                let mut extensions_path = garde::util::nested_path!(parent, "extensions");
+                for (index, extension) in extensions.iter().enumerate() {
+                    if extension.is_empty() || extension.contains(['.', '/', '\\']) {
+                        let mut path = garde::util::nested_path!(extensions_path, index);
+                        report.append(
+                            path(),
+                            garde::Error::new(
+                                "extension must not be empty or contain . or slashes",
+                            ),
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
 
-    let v: u16 = s.parse().map_err(|_| garde::Error::new("invalid number"))?;
-    if v > 255 {
-        return Err(garde::Error::new("rgb exceeded 255 bound"));
+        // Default values must satisfy the constraints of the setting
+        if let Some(default) = self.default_value()
+            && let Err(error) = self.validate_value(&default, ctx)
+        {
+            let mut path = garde::util::nested_path!(parent, "default");
+            report.append(path(), error);
+        }
     }
-
-    Ok(())
 }
 
-/// Parse an alpha channel (0–1)
-fn parse_alpha(s: &str) -> garde::Result {
-    if s.parse::<f64>()
-        .is_ok_and(|value| (0.0..=1.0).contains(&value))
-    {
-        return Ok(());
-    }
+/// Validates that a string is a valid color value supports:
+/// - hex
+/// - rgb/rgba
+/// - hsl/hsla
+/// - hwb
+/// - oklab/oklch
+///
+/// Both the legacy comma separated syntax and the CSS Color Level 4
+/// space separated syntax (i.e rgb(255 0 0 / 50%)) are supported
+///
+/// Does not check for named colors, use [validate_color_with_mode] to
+/// accept them
+pub fn validate_color(value: &str, _context: &()) -> garde::Result {
+    validate_color_with_mode(value, &ColorMode::Strict)
+}
 
-    Err(garde::Error::new("invalid alpha"))
+/// Validates a color value like [validate_color], named colors are only
+/// accepted when the `mode` is [ColorMode::Lenient]
+pub fn validate_color_with_mode(value: &str, mode: &ColorMode) -> garde::Result {
+    Color::parse(value, *mode).map(|_| ())
 }
 
-/// Parse hue (0–360)
-fn parse_hue(s: &str) -> garde::Result {
-    let value: u16 = s.parse().map_err(|_| garde::Error::new("invalid hue"))?;
-    if value > 360 {
-        return Err(garde::Error::new("hue must not be greater than 360"));
+impl Validate for ManifestVersion {
+    type Context = ValidationOptions;
+
+    fn validate_into(
+        &self,
+        _options: &ValidationOptions,
+        parent: &mut dyn FnMut() -> Path,
+        report: &mut Report,
+    ) {
+        if !self.is_strict() {
+            report.append(
+                parent(),
+                garde::Error::new(format!(
+                    "version must be a strict semver version (i.e {self}), prefixes and leading zeros are not allowed"
+                )),
+            );
+        }
     }
-
-    Ok(())
 }
 
-/// Parse percentage (0–100%)
-fn parse_percentage(s: &str) -> garde::Result {
-    let number = s
-        .strip_suffix('%')
-        .ok_or_else(|| garde::Error::new("missing % sign"))?;
-
-    let value: u8 = number
-        .parse()
-        .map_err(|_| garde::Error::new("invalid percent"))?;
-
-    if value > 100 {
-        return Err(garde::Error::new("percent > 100"));
+impl Validate for Color {
+    type Context = ValidationOptions;
+
+    fn validate_into(
+        &self,
+        options: &ValidationOptions,
+        parent: &mut dyn FnMut() -> Path,
+        report: &mut Report,
+    ) {
+        if self.is_named() && options.color_mode == ColorMode::Strict {
+            report.append(parent(), garde::Error::new("named colors are not allowed"));
+        }
     }
-
-    Ok(())
 }
 
 #[cfg(test)]
@@ -359,6 +656,59 @@ mod tests {
         assert!(validate_name("Action-", &()).is_err());
     }
 
+    #[test]
+    fn validate_path_allows_relative_paths() {
+        assert!(validate_path("icon.svg", &()).is_ok());
+        assert!(validate_path("images/icon.svg", &()).is_ok());
+        assert!(validate_path("./bin/index.js", &()).is_ok());
+    }
+
+    #[test]
+    fn validate_path_fails_on_absolute_paths() {
+        assert!(validate_path("/etc/passwd", &()).is_err());
+        assert!(validate_path("C:/Windows/System32", &()).is_err());
+    }
+
+    #[test]
+    fn validate_path_fails_on_escaping_paths() {
+        assert!(validate_path("../../etc/passwd", &()).is_err());
+        assert!(validate_path("images/../../secret", &()).is_err());
+    }
+
+    #[test]
+    fn validate_path_fails_on_backslashes() {
+        assert!(validate_path("bin\\program.exe", &()).is_err());
+    }
+
+    #[test]
+    fn validate_path_fails_on_empty_segments() {
+        assert!(validate_path("", &()).is_err());
+        assert!(validate_path("images//icon.svg", &()).is_err());
+        assert!(validate_path("images/", &()).is_err());
+    }
+
+    #[test]
+    fn validate_network_host_allows_hosts() {
+        assert!(validate_network_host("example.com", &()).is_ok());
+        assert!(validate_network_host("api.example-1.com", &()).is_ok());
+        assert!(validate_network_host("localhost:8080", &()).is_ok());
+        assert!(validate_network_host("127.0.0.1", &()).is_ok());
+        assert!(validate_network_host("*.example.com", &()).is_ok());
+        assert!(validate_network_host("*", &()).is_ok());
+    }
+
+    #[test]
+    fn validate_network_host_fails_on_invalid_hosts() {
+        assert!(validate_network_host("", &()).is_err());
+        assert!(validate_network_host("https://example.com", &()).is_err());
+        assert!(validate_network_host("example.com/path", &()).is_err());
+        assert!(validate_network_host("example.com:0", &()).is_err());
+        assert!(validate_network_host("example.com:http", &()).is_err());
+        assert!(validate_network_host("api.*.com", &()).is_err());
+        assert!(validate_network_host("example..com", &()).is_err());
+        assert!(validate_network_host("-example.com", &()).is_err());
+    }
+
     fn color_ok(value: &str) {
         assert!(
             validate_color(value, &()).is_ok(),
@@ -427,12 +777,13 @@ mod tests {
         color_ok("hsl(0,0%,0%)");
         color_ok("hsl(360,100%,50%)");
         color_ok("hsl(180, 50%, 25%)");
+        color_ok("hsl(361,50%,50%)"); // hue wraps around
     }
 
     #[test]
     fn test_invalid_hsl_colors() {
         color_err("hsl()");
-        color_err("hsl(361,50%,50%)"); // hue too high
+        color_err("hsl(1.,50%,50%)"); // invalid number
         color_err("hsl(180,101%,50%)"); // percent > 100
         color_err("hsl(180,50,50)"); // missing %
     }
@@ -442,20 +793,123 @@ mod tests {
         color_ok("hsla(0,0%,0%,0)");
         color_ok("hsla(360,100%,50%,1)");
         color_ok("hsla(180, 50%, 25%, 0.75)");
+        color_ok("hsla(-30,50%,50%,0.5)"); // hue wraps around
     }
 
     #[test]
     fn test_invalid_hsla_colors() {
         color_err("hsla(180,50%,50%)"); // missing alpha
         color_err("hsla(180,50%,50%,2)"); // alpha too big
-        color_err("hsla(361,50%,50%,0.5)"); // hue too big
+        color_err("hsla(deg,50%,50%,0.5)"); // missing hue
         color_err("hsla(180,50,50%,0.5)"); // missing % in second arg
     }
 
+    #[test]
+    fn test_valid_space_separated_colors() {
+        color_ok("rgb(255 0 0)");
+        color_ok("rgb(255 0 0 / 50%)");
+        color_ok("rgb(255 0 0 / 0.5)");
+        color_ok("rgba(10% 20% 30% / 1)");
+        color_ok("hsl(120 50% 50%)");
+        color_ok("hsl(120 50% 50% / 25%)");
+    }
+
+    #[test]
+    fn test_invalid_space_separated_colors() {
+        color_err("rgb(255 0)"); // not enough parts
+        color_err("rgb(255 0 0 0)"); // alpha without slash
+        color_err("rgb(255 0 0 /)"); // missing alpha
+        color_err("rgb(255 0 0 / 0.5 / 1)"); // multiple alpha
+        color_err("rgb(255, 0, 0 / 0.5)"); // mixed syntax
+    }
+
+    #[test]
+    fn test_valid_hue_angle_units() {
+        color_ok("hsl(120deg 50% 50%)");
+        color_ok("hsl(1.5rad 50% 50%)");
+        color_ok("hsl(200grad 50% 50%)");
+        color_ok("hsl(0.5turn 50% 50%)");
+        color_ok("hsla(120deg, 50%, 50%, 0.5)");
+        color_ok("hsl(2turn 50% 50%)"); // hue wraps around
+        color_ok("hsl(-10deg 50% 50%)");
+    }
+
+    #[test]
+    fn test_invalid_hue_angle_units() {
+        color_err("hsl(120foo 50% 50%)"); // unknown unit
+        color_err("hsl(120 deg 50% 50%)"); // separated unit
+    }
+
+    #[test]
+    fn test_valid_fractional_values() {
+        color_ok("rgb(127.5, 0, 0)");
+        color_ok("rgb(50.5%, 20.25%, 0%)");
+        color_ok("hsl(180.5, 50.5%, 25.75%)");
+        color_ok("rgba(0, 0, 0, .5)");
+    }
+
+    #[test]
+    fn test_invalid_fractional_values() {
+        color_err("rgb(255.5, 0, 0)"); // out of range
+        color_err("rgb(100.5%, 0%, 0%)"); // percent > 100
+        color_err("rgb(inf, 0, 0)"); // not a number
+    }
+
+    #[test]
+    fn test_valid_hwb_colors() {
+        color_ok("hwb(0 0% 0%)");
+        color_ok("hwb(120deg 20% 30% / 0.5)");
+        color_ok("hwb(0 80% 80%)"); // normalized
+    }
+
+    #[test]
+    fn test_invalid_hwb_colors() {
+        color_err("hwb(0, 0%, 0%)"); // legacy syntax not supported
+        color_err("hwb(0 0 0)"); // missing %
+    }
+
+    #[test]
+    fn test_valid_oklab_oklch_colors() {
+        color_ok("oklab(0.5 0.1 -0.1)");
+        color_ok("oklab(50% 25% -25% / 0.5)");
+        color_ok("oklch(0.7 0.15 180)");
+        color_ok("oklch(70% 0.15 180deg / 50%)");
+    }
+
+    #[test]
+    fn test_invalid_oklab_oklch_colors() {
+        color_err("oklab(2 0 0)"); // lightness > 1
+        color_err("oklch(0.5 -0.1 180)"); // negative chroma
+        color_err("oklch(0.5, 0.1, 180)"); // legacy syntax not supported
+        color_err("oklch(0.5 0.1)"); // missing hue
+    }
+
+    #[test]
+    fn test_named_colors_lenient_mode() {
+        let lenient = |value: &str| validate_color_with_mode(value, &ColorMode::Lenient);
+
+        assert!(lenient("white").is_ok());
+        assert!(lenient("Transparent").is_ok());
+        assert!(lenient("rebeccapurple").is_ok());
+        assert!(lenient("#fff").is_ok());
+        assert!(lenient("currentcolor").is_err());
+        assert!(lenient("notacolor").is_err());
+    }
+
     #[test]
     fn test_invalid_general_cases() {
         color_err("blue"); // named colors not supported
         color_err(""); // empty string
         color_err("123"); // junk input
     }
+
+    #[test]
+    fn test_empty_color_reported() {
+        for mode in [ColorMode::Strict, ColorMode::Lenient] {
+            for value in ["", "   "] {
+                let error = Color::parse(value, mode).unwrap_err();
+                assert_eq!(error.message(), "empty color");
+            }
+        }
+    }
 }
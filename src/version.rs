@@ -0,0 +1,245 @@
+//! # Version
+//!
+//! Semver version numbers used by plugins and icon packs
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize, de};
+use std::{cmp::Ordering, fmt::Display, hash::Hash, str::FromStr};
+use thiserror::Error;
+
+/// Semver compatible version number of a plugin or icon pack (i.e 0.1.0)
+///
+/// Versions are ordered following semver precedence rules so they
+/// can be used to determine whether an update is available
+//
+// Deserializing accepts loose versions (i.e v1.2.3) so they can be
+// reported when validating rather than failing to parse
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(transparent)]
+#[schemars(with = "String", example = "0.1.0")]
+pub struct ManifestVersion(pub node_semver::Version, #[serde(skip)] Strictness);
+
+/// Whether a [ManifestVersion] was written as a strict semver version
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Strictness {
+    #[default]
+    Strict,
+    Loose,
+}
+
+impl ManifestVersion {
+    /// Create a new version from the major, minor and patch components
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self(
+            node_semver::Version::new(major, minor, patch),
+            Strictness::Strict,
+        )
+    }
+
+    /// Whether the version was written as a strict semver version, loose
+    /// versions (i.e v1.2.3, 01.2.3) are rejected when validating
+    pub fn is_strict(&self) -> bool {
+        self.1 == Strictness::Strict
+    }
+}
+
+impl From<node_semver::Version> for ManifestVersion {
+    fn from(value: node_semver::Version) -> Self {
+        Self(value, Strictness::Strict)
+    }
+}
+
+// Versions are compared without their strictness
+
+impl PartialEq for ManifestVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for ManifestVersion {}
+
+impl PartialOrd for ManifestVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ManifestVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl Hash for ManifestVersion {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl FromStr for ManifestVersion {
+    type Err = VersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The semver parser is loose and accepts versions such as v1.2.3
+        if !is_strict(s) {
+            return Err(VersionError::NotStrict);
+        }
+
+        Ok(Self(node_semver::Version::parse(s)?, Strictness::Strict))
+    }
+}
+
+impl TryFrom<&str> for ManifestVersion {
+    type Error = VersionError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::from_str(value)
+    }
+}
+
+impl Display for ManifestVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl AsRef<node_semver::Version> for ManifestVersion {
+    fn as_ref(&self) -> &node_semver::Version {
+        &self.0
+    }
+}
+
+/// Error parsing a [ManifestVersion]
+#[derive(Debug, Error)]
+pub enum VersionError {
+    /// Version is not a valid semver version
+    #[error(transparent)]
+    Semver(#[from] node_semver::SemverError),
+
+    /// Version has a prefix, surrounding whitespace or leading zeros
+    /// (i.e v1.2.3, 01.2.3) which strict semver does not allow
+    #[error("version is not a strict semver version")]
+    NotStrict,
+}
+
+/// Check the major, minor, patch and pre-release components of `value`
+/// follow strict semver, the remaining syntax is left to the parser
+fn is_strict(value: &str) -> bool {
+    let value = value.split_once('+').map_or(value, |(version, _)| version);
+    let (core, pre_release) = match value.split_once('-') {
+        Some((core, pre_release)) => (core, Some(pre_release)),
+        None => (value, None),
+    };
+
+    let is_numeric = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    let has_leading_zero = |part: &str| part.len() > 1 && part.starts_with('0');
+    let is_valid_identifier = |part: &str| match part {
+        "" => false,
+        part if is_numeric(part) => !has_leading_zero(part),
+        _ => true,
+    };
+
+    let parts: Vec<&str> = core.split('.').collect();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|part| is_numeric(part) && !has_leading_zero(part))
+        && pre_release.is_none_or(|pre_release| pre_release.split('.').all(is_valid_identifier))
+}
+
+impl<'de> Deserialize<'de> for ManifestVersion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        let version = node_semver::Version::parse(&value).map_err(|_| {
+            de::Error::custom(format!(
+                "invalid version \"{value}\", expected a semver version (i.e 1.0.0)"
+            ))
+        })?;
+
+        // Loose versions are reported when validating
+        let strictness = match is_strict(&value) {
+            true => Strictness::Strict,
+            false => Strictness::Loose,
+        };
+
+        Ok(Self(version, strictness))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_valid_versions() {
+        assert_eq!(
+            "1.2.3".parse::<ManifestVersion>().unwrap(),
+            ManifestVersion::new(1, 2, 3)
+        );
+        assert!("1.2.3-beta.1".parse::<ManifestVersion>().is_ok());
+        assert!("1.2.3+build.5".parse::<ManifestVersion>().is_ok());
+    }
+
+    #[test]
+    fn test_parse_invalid_versions() {
+        assert!("latest".parse::<ManifestVersion>().is_err());
+        assert!("1.x".parse::<ManifestVersion>().is_err());
+        assert!("1.0".parse::<ManifestVersion>().is_err());
+        assert!("".parse::<ManifestVersion>().is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_loose_versions() {
+        assert!(matches!(
+            "v1.2.3".parse::<ManifestVersion>(),
+            Err(VersionError::NotStrict)
+        ));
+        assert!(matches!(
+            "01.2.3".parse::<ManifestVersion>(),
+            Err(VersionError::NotStrict)
+        ));
+        assert!("=1.2.3".parse::<ManifestVersion>().is_err());
+        assert!(" 1.2.3".parse::<ManifestVersion>().is_err());
+        assert!("1.2.3-01".parse::<ManifestVersion>().is_err());
+
+        // Leading zeros are allowed in alphanumeric identifiers and build metadata
+        assert!("1.2.3-0beta".parse::<ManifestVersion>().is_ok());
+        assert!("1.2.3+001".parse::<ManifestVersion>().is_ok());
+        assert!("0.10.0".parse::<ManifestVersion>().is_ok());
+    }
+
+    #[test]
+    fn test_deserialize_invalid_version_errors() {
+        let error = serde_json::from_str::<ManifestVersion>("\"latest\"").unwrap_err();
+        assert!(error.to_string().contains("invalid version \"latest\""));
+    }
+
+    #[test]
+    fn test_deserialize_loose_version() {
+        let version = serde_json::from_str::<ManifestVersion>("\"v1.2.3\"").unwrap();
+        assert!(!version.is_strict());
+        assert_eq!(version, ManifestVersion::new(1, 2, 3));
+
+        let version = serde_json::from_str::<ManifestVersion>("\"01.2.3\"").unwrap();
+        assert!(!version.is_strict());
+
+        let version = serde_json::from_str::<ManifestVersion>("\"1.2.3\"").unwrap();
+        assert!(version.is_strict());
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        let v1: ManifestVersion = "1.0.0".parse().unwrap();
+        let v1_1: ManifestVersion = "1.1.0".parse().unwrap();
+        let v1_pre: ManifestVersion = "1.0.0-beta.1".parse().unwrap();
+        let v10: ManifestVersion = "10.0.0".parse().unwrap();
+
+        assert!(v1 < v1_1);
+        assert!(v1_pre < v1);
+        assert!(v1_1 < v10);
+    }
+}
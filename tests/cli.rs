@@ -0,0 +1,211 @@
+//! Tests for the exit codes and output of the command line tool
+
+#![cfg(feature = "cli")]
+
+use assert_cmd::{Command, cargo::cargo_bin_cmd};
+use std::process::{Output, Stdio};
+use tempfile::TempDir;
+
+const VALID: &str = r#"{
+  "manifest_version": 2,
+  "plugin": {
+    "id": "com.example.test",
+    "name": "Test",
+    "version": "0.1.0",
+    "authors": ["Example"],
+    "description": "Example plugin",
+    "icon": "images/icon.svg"
+  },
+  "category": { "label": "Test", "icon": "images/icon.svg" },
+  "actions": {
+    "example": {
+      "label": "Example",
+      "description": "Example action",
+      "icon": "images/example.svg",
+      "inspector": "inspector/index.html"
+    }
+  }
+}
+"#;
+
+const INVALID: &str = r#"{
+  "manifest_version": 2,
+  "plugin": { "id": "com.example.test", "name": "Test", "version": "0.1.0", "authors": [] },
+  "category": { "label": "Test" },
+  "actions": {
+    "example": { "label": "" }
+  }
+}
+"#;
+
+const INCOMPLETE: &str = r#"{
+  "manifest_version": 2,
+  "plugin": { "id": "com.example.test", "name": "Test", "version": "0.1.0", "authors": [] },
+  "category": { "label": "Test" },
+  "actions": {
+    "example": { "label": "Example" }
+  }
+}
+"#;
+
+fn cli() -> Command {
+    cargo_bin_cmd!("tilepad-manifest")
+}
+
+fn write_manifest(dir: &TempDir, name: &str, source: &str) -> String {
+    let path = dir.path().join(name);
+    std::fs::write(&path, source).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn test_validate_valid_manifest() {
+    let dir = TempDir::new().unwrap();
+    let path = write_manifest(&dir, "manifest.json", VALID);
+
+    let assert = cli().args(["validate", &path]).assert().code(0);
+    assert!(stdout(assert.get_output()).contains("manifest is valid"));
+}
+
+#[test]
+fn test_validate_invalid_manifest() {
+    let dir = TempDir::new().unwrap();
+    let path = write_manifest(&dir, "manifest.json", INVALID);
+
+    let assert = cli().args(["validate", &path]).assert().code(1);
+    let stderr = stderr(assert.get_output());
+    assert!(stderr.contains(&format!("{path}:6:")), "{stderr}");
+    assert!(stderr.contains("actions.example.label"), "{stderr}");
+}
+
+#[test]
+fn test_validate_json_output() {
+    let dir = TempDir::new().unwrap();
+    let path = write_manifest(&dir, "manifest.json", INVALID);
+
+    let assert = cli().args(["validate", "--json", &path]).assert().code(1);
+    let report: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(report["valid"], false);
+    assert_eq!(report["kind"], "plugin");
+    assert_eq!(report["diagnostics"][0]["path"], "actions.example.label");
+}
+
+#[test]
+fn test_validate_missing_bundle_files() {
+    let dir = TempDir::new().unwrap();
+    let path = write_manifest(&dir, "manifest.json", VALID);
+
+    cli().args(["validate", &path]).assert().code(0);
+    cli().args(["validate", "--bundle", &path]).assert().code(1);
+}
+
+#[test]
+fn test_validate_missing_file() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("missing.json");
+
+    let assert = cli()
+        .args(["validate", &path.to_string_lossy()])
+        .assert()
+        .code(2);
+    assert!(stderr(assert.get_output()).starts_with("error: failed to read"));
+}
+
+#[test]
+fn test_lint() {
+    let dir = TempDir::new().unwrap();
+
+    let path = write_manifest(&dir, "manifest.json", VALID);
+    cli().args(["lint", &path]).assert().code(0);
+
+    let path = write_manifest(&dir, "incomplete.json", INCOMPLETE);
+    let assert = cli().args(["lint", &path]).assert().code(1);
+    let stderr = stderr(assert.get_output());
+    assert!(stderr.contains("warning[missing-authors]"), "{stderr}");
+
+    let path = write_manifest(&dir, "invalid.json", INVALID);
+    cli().args(["lint", &path]).assert().code(1);
+}
+
+#[test]
+fn test_fmt() {
+    let dir = TempDir::new().unwrap();
+    let unformatted = VALID.replace("\n  ", "\n");
+    let path = write_manifest(&dir, "manifest.json", &unformatted);
+
+    let assert = cli().args(["fmt", "--check", &path]).assert().code(1);
+    assert!(stderr(assert.get_output()).contains("manifest is not formatted"));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), unformatted);
+
+    cli().args(["fmt", &path]).assert().code(0);
+    cli().args(["fmt", "--check", &path]).assert().code(0);
+
+    let formatted = std::fs::read_to_string(&path).unwrap();
+    let expected: serde_json::Value = serde_json::from_str(VALID).unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&formatted).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn test_fmt_invalid_manifest() {
+    let dir = TempDir::new().unwrap();
+    let path = write_manifest(&dir, "manifest.json", INVALID);
+
+    cli().args(["fmt", &path]).assert().code(1);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), INVALID);
+}
+
+#[test]
+fn test_fmt_json5_refused() {
+    let dir = TempDir::new().unwrap();
+    let path = write_manifest(&dir, "manifest.json5", "{ manifest_version: 2 }");
+
+    let assert = cli().args(["fmt", &path]).assert().code(2);
+    assert!(stderr(assert.get_output()).contains("JSON5 manifests cannot be formatted"));
+}
+
+#[test]
+fn test_closed_stdout_exits_quietly() {
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin!("tilepad-manifest"))
+        .args(["types", "--kind", "plugin"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // Close the read end before the output is written
+    drop(child.stdout.take());
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(stderr(&output), "");
+}
+
+#[test]
+fn test_new_creates_valid_project() {
+    let dir = TempDir::new().unwrap();
+    let project = dir.path().join("project");
+
+    let assert = cli()
+        .args(["new", &project.to_string_lossy()])
+        .args(["--id", "com.example.test", "--name", "Test"])
+        .assert()
+        .code(0);
+    assert!(stdout(assert.get_output()).contains("created"));
+
+    let manifest = project.join("manifest.json");
+    cli()
+        .args(["validate", "--bundle", &manifest.to_string_lossy()])
+        .assert()
+        .code(0);
+}